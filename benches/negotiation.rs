@@ -0,0 +1,42 @@
+//! Compares resolution negotiation by a single directory listing against probing every resolution folder.
+
+use std::path::PathBuf;
+
+use bevy_ambient_cg::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const RESOLUTIONS: [&str; 6] = ["16K", "12K", "8K", "4K", "2K", "1K"];
+
+const BENCH_001: AmbientCGMaterial = AmbientCGMaterial {
+    name: "Bench001",
+    // only 1K exists, so a 16K request falls back through every resolution
+    resolution: AmbientCGResolution::SixteenK,
+    ..AmbientCGMaterial::DEFAULT
+};
+
+/// Builds an asset root holding a 1K material among a few unrelated folders.
+fn asset_root() -> PathBuf {
+    let root = std::env::temp_dir().join("bevy_ambient_cg_bench");
+    let materials = root.join("assets/materials");
+    for folder in ["Bench001_1K-JPG", "Bench002_2K-JPG", "Bench003_4K-JPG"] {
+        std::fs::create_dir_all(materials.join(folder)).unwrap();
+    }
+    root
+}
+
+fn negotiation(c: &mut Criterion) {
+    let root = asset_root();
+    let config = AmbientCGConfig { asset_root: Some(root.join("assets")), ..Default::default() };
+    let materials = root.join("assets").join(&config.materials_path);
+
+    let mut group = c.benchmark_group("negotiate 16K down to 1K");
+    group.bench_function("directory scan", |b| b.iter(|| BENCH_001.available_resolutions(&config)));
+    // what negotiation did before, one stat per resolution until a folder exists
+    group.bench_function("stat per resolution", |b| b.iter(|| {
+        RESOLUTIONS.iter().find(|resolution| materials.join(format!("Bench001_{}-JPG", resolution)).exists())
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, negotiation);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+//! Lays out every material found under `assets/materials` on a grid of spheres, at the resolution each was
+//! downloaded at. Run it with `cargo run --example gallery` after downloading a few materials from
+//! https://ambientcg.com/ into `assets/materials`.
+
+use bevy::prelude::*;
+use bevy_ambient_cg::*;
+
+/// Distance between the centers of neighbouring spheres.
+const SPACING: f32 = 2.5;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, AmbientCGPlugin::default()))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>
+) {
+    let discovered = config.discover();
+    if discovered.is_empty() {
+        warn!("no materials found in assets/{}", config.materials_path.display());
+    }
+    let columns = (discovered.len() as f32).sqrt().ceil().max(1.0) as usize;
+    let sphere = meshes.add(Sphere::new(1.0).mesh().uv(64, 32));
+
+    for (index, (name, resolution, variant)) in discovered.iter().enumerate() {
+        let material = AmbientCGMaterial { name, resolution: *resolution, variant: *variant, ..AmbientCGMaterial::DEFAULT };
+        let handle = match material.try_load(&asset_server, &mut materials, &config, &state) {
+            Ok(handle) => handle,
+            Err(error) => {
+                warn!("skipping {}: {}", name, error);
+                continue;
+            }
+        };
+        let (column, row) = (index % columns, index / columns);
+        commands.spawn((
+            Mesh3d(sphere.clone()),
+            MeshMaterial3d(handle),
+            Transform::from_xyz(column as f32 * SPACING, 0.0, row as f32 * SPACING),
+            Name::new(name.clone())
+        ));
+    }
+
+    let center = (columns - 1) as f32 * SPACING / 2.0;
+    let target = Vec3::new(center, 0.0, center);
+    commands.spawn((DirectionalLight::default(), Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y)));
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_translation(target + Vec3::new(0.0, columns as f32 * SPACING, columns as f32 * SPACING + 2.0))
+            .looking_at(target, Vec3::Y)
+    ));
+}
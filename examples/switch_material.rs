@@ -0,0 +1,88 @@
+//! Swaps the material of a plane at runtime. Press space to switch between two materials and 1 or 2 to reload the
+//! current one at 1K or 2K.
+//!
+//! Download `Ground037` and `Rock023` from https://ambientcg.com/ into `assets/materials`, e.g.
+//! `assets/materials/Ground037_1K-JPG/Ground037_1K-JPG_Color.jpg`, then run `cargo run --example switch_material`.
+
+use bevy::prelude::*;
+use bevy_ambient_cg::*;
+
+const GROUND: AmbientCGMaterial = AmbientCGMaterial {
+    name: "Ground037",
+    resolution: AmbientCGResolution::TwoK,
+    uv_scale: Some(Vec2::splat(4.0)),
+    ..AmbientCGMaterial::DEFAULT
+};
+
+const ROCK: AmbientCGMaterial = AmbientCGMaterial {
+    name: "Rock023",
+    resolution: AmbientCGResolution::TwoK,
+    uv_scale: Some(Vec2::splat(4.0)),
+    ..AmbientCGMaterial::DEFAULT
+};
+
+const MATERIALS: [AmbientCGMaterial; 2] = [GROUND, ROCK];
+
+/// Both loaded materials and the index of the one on the plane.
+#[derive(Resource)]
+struct Materials {
+    handles: [Handle<StandardMaterial>; 2],
+    current: usize
+}
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, AmbientCGPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, switch_material)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    let handles = MATERIALS.map(|material| material.load(&asset_server, &mut materials, &config, &state));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(8.0, 8.0))),
+        MeshMaterial3d(handles[0].clone())
+    ));
+    commands.insert_resource(Materials { handles, current: 0 });
+
+    commands.spawn((DirectionalLight::default(), Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y)));
+    commands.spawn((Camera3d::default(), Transform::from_xyz(0.0, 6.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y)));
+}
+
+fn switch_material(
+    keys: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>,
+    mut loaded: ResMut<Materials>,
+    mut planes: Query<&mut MeshMaterial3d<StandardMaterial>>
+) {
+    if keys.just_pressed(KeyCode::Space) {
+        loaded.current = 1 - loaded.current;
+        for mut material in planes.iter_mut() {
+            material.0 = loaded.handles[loaded.current].clone();
+        }
+    }
+
+    let resolution = if keys.just_pressed(KeyCode::Digit1) {
+        AmbientCGResolution::OneK
+    } else if keys.just_pressed(KeyCode::Digit2) {
+        AmbientCGResolution::TwoK
+    } else {
+        return;
+    };
+    // every entity using the handle picks up the new textures
+    let material = &MATERIALS[loaded.current];
+    if let Err(error) = material.swap_resolution(&loaded.handles[loaded.current], resolution, &asset_server, &mut materials, &config, &state) {
+        warn!("could not switch {} to {}: {}", material.name, resolution, error);
+    }
+}
@@ -0,0 +1,123 @@
+//! Integration with the dynamic assets of `bevy_asset_loader`, enabled by the `bevy_asset_loader` feature.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_asset_loader::dynamic_asset::{DynamicAsset, DynamicAssetCollection, DynamicAssetType, DynamicAssets};
+use serde::Deserialize;
+
+use crate::{AmbientCGConfig, AmbientCGImportError, AmbientCGMaterial, AmbientCGState};
+
+/// A dynamic asset file mapping keys to AmbientCG materials. Load it with `bevy_common_assets`, e.g.
+/// `RonAssetPlugin::<AmbientCGDynamicAssets>::new(&["ambientcg.ron"])`, and register it on the loading state with
+/// `register_dynamic_asset_collection::<AmbientCGDynamicAssets>()`.
+#[derive(Deserialize, Asset, TypePath)]
+pub struct AmbientCGDynamicAssets(pub HashMap<String, AmbientCGDynamicAsset>);
+
+impl DynamicAssetCollection for AmbientCGDynamicAssets {
+    fn register(&self, dynamic_assets: &mut DynamicAssets) {
+        for (key, asset) in self.0.iter() {
+            dynamic_assets.register_asset(key, Box::new(asset.clone()));
+        }
+    }
+}
+
+/// An AmbientCG material declared in a dynamic asset file, resolving to a `Handle<StandardMaterial>`:
+/// `(name: "Rock023", resolution: Some("2K"), subfolder: Some("rocks"))`.
+///
+/// The material is loaded through `AmbientCGMaterial::try_load` as soon as its collection file is, so it is counted and
+/// registered like any other load and the loading state waits for its textures.
+#[derive(Deserialize, Clone, Debug)]
+pub struct AmbientCGDynamicAsset {
+    pub name: String,
+    /// One of `"1K"` to `"16K"`, `AmbientCGConfig::default_resolution` when omitted.
+    #[serde(default)]
+    pub resolution: Option<String>,
+    #[serde(default)]
+    pub subfolder: Option<String>,
+    #[serde(default)]
+    pub uv_scale: Option<(f32, f32)>,
+    // set by load_ambient_cg_dynamic_assets, None if the material failed to load so build can report the error
+    #[serde(skip)]
+    loaded: OnceLock<Option<(Handle<StandardMaterial>, Vec<UntypedHandle>)>>
+}
+
+impl AmbientCGDynamicAsset {
+    fn material(&self, config: &AmbientCGConfig) -> Result<AmbientCGMaterial<'_>, AmbientCGImportError> {
+        let resolution = match &self.resolution {
+            Some(resolution) => resolution.parse()?,
+            None => config.default_resolution
+        };
+        Ok(AmbientCGMaterial {
+            name: &self.name,
+            resolution,
+            subfolder: self.subfolder.as_deref(),
+            uv_scale: self.uv_scale.map(|(x, y)| Vec2::new(x, y)),
+            ..AmbientCGMaterial::DEFAULT
+        })
+    }
+}
+
+impl DynamicAsset for AmbientCGDynamicAsset {
+    fn load(&self, _asset_server: &AssetServer) -> Vec<UntypedHandle> {
+        match self.loaded.get() {
+            Some(Some((_, textures))) => textures.clone(),
+            _ => Vec::new()
+        }
+    }
+
+    fn build(&self, world: &mut World) -> Result<DynamicAssetType, anyhow::Error> {
+        if let Some(Some((handle, _))) = self.loaded.get() {
+            return Ok(DynamicAssetType::Single(handle.clone().untyped()));
+        }
+        let mut system_state = SystemState::<(
+            Res<AssetServer>,
+            ResMut<Assets<StandardMaterial>>,
+            Res<AmbientCGConfig>,
+            Res<AmbientCGState>
+        )>::new(world);
+        let (asset_server, mut materials, config, state) = system_state.get_mut(world);
+        let handle = self.material(&config)?.try_load(&asset_server, &mut materials, &config, &state)?;
+        Ok(DynamicAssetType::Single(handle.untyped()))
+    }
+}
+
+/// Loads the materials of every newly loaded `AmbientCGDynamicAssets` file. `DynamicAsset::load` only gets the asset
+/// server, so this runs right after the file lands in `Assets` and before `bevy_asset_loader` registers it.
+pub(crate) fn load_ambient_cg_dynamic_assets(
+    collections: Res<Assets<AmbientCGDynamicAssets>>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    for (_, collection) in collections.iter() {
+        for asset in collection.0.values().filter(|asset| asset.loaded.get().is_none()) {
+            let loaded = asset.material(&config)
+                .and_then(|material| material.try_load(&asset_server, &mut materials, &config, &state))
+                .ok()
+                .map(|handle| {
+                    let textures = materials.get(&handle).map(material_textures).unwrap_or_default();
+                    (handle, textures)
+                });
+            let _ = asset.loaded.set(loaded);
+        }
+    }
+}
+
+fn material_textures(material: &StandardMaterial) -> Vec<UntypedHandle> {
+    [
+        &material.base_color_texture,
+        &material.metallic_roughness_texture,
+        &material.normal_map_texture,
+        &material.occlusion_texture,
+        &material.thickness_texture,
+        &material.specular_transmission_texture
+    ]
+    .into_iter()
+    .flatten()
+    .map(|texture| texture.clone().untyped())
+    .collect()
+}
@@ -0,0 +1,64 @@
+//! AmbientCG HDRIs as environment images, enabled by the `hdri` feature.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use crate::{
+    expect_loaded, resource_exists, AmbientCGConfig, AmbientCGErrorType, AmbientCGImportError, AmbientCGMaterial, AmbientCGResolution,
+    AmbientCGResolutionPolicy, AmbientCGVariant
+};
+
+const HDRI_EXTENSIONS: [&str; 2] = ["exr", "hdr"];
+
+/// An AmbientCG HDRI, e.g. `DayEnvironmentHDRI019`, stored as `{name}_{resolution}-HDR.exr` (or `.hdr`) in the
+/// materials path next to the materials, or in `subfolder` of it.
+///
+/// Resolution is negotiated like it is for materials, by `AmbientCGConfig::resolution_negotiator` following
+/// `AmbientCGConfig::resolution_policy`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AmbientCGEnvironment<'a> {
+    pub name: &'a str,
+    pub resolution: AmbientCGResolution,
+    pub subfolder: Option<&'a str>
+}
+
+impl AmbientCGEnvironment<'_> {
+    /// Loads the HDRI as an equirectangular `Image`, panicking if it cannot be found.
+    ///
+    /// `Skybox` and `EnvironmentMapLight` sample cubemaps, so the image has to be reprojected to a cubemap (e.g. with
+    /// a compute pass or an offline tool) before it can light a scene.
+    pub fn load_environment(&self, asset_server: &AssetServer, config: &AmbientCGConfig) -> Handle<Image> {
+        expect_loaded(self.try_load_environment(asset_server, config))
+    }
+    pub fn try_load_environment(&self, asset_server: &AssetServer, config: &AmbientCGConfig) -> Result<Handle<Image>, AmbientCGImportError> {
+        if config.headless {
+            return Ok(Handle::default());
+        }
+        Ok(asset_server.load(self.find(config)?))
+    }
+    /// Finds the HDRI file at the resolution the negotiator picks among the ones on disk, if negotiation is enabled.
+    pub(crate) fn find(&self, config: &AmbientCGConfig) -> Result<PathBuf, AmbientCGImportError> {
+        let material = AmbientCGMaterial {
+            name: self.name,
+            resolution: self.resolution,
+            subfolder: self.subfolder,
+            variant: AmbientCGVariant::Hdr,
+            ..AmbientCGMaterial::DEFAULT
+        };
+        let resolution = match config.resolution_policy {
+            AmbientCGResolutionPolicy::Negotiate if config.resolution_negotiation => config.resolution_negotiator.negotiate(&material, config)?,
+            _ => self.resolution
+        };
+        let material = AmbientCGMaterial { resolution, ..material };
+        find_hdri(&material.materials_path(config).join(material.folder_name()), config)
+            .ok_or(AmbientCGImportError(AmbientCGErrorType::NotFound))
+    }
+}
+
+fn find_hdri(file_path: &Path, config: &AmbientCGConfig) -> Option<PathBuf> {
+    HDRI_EXTENSIONS
+        .iter()
+        .map(|extension| file_path.with_extension(extension))
+        .find(|path| resource_exists(path, config))
+}
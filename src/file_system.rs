@@ -0,0 +1,91 @@
+//! Where material files are probed and read from, see `AmbientCGConfig::file_system`.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::default_resource_path;
+
+/// Files behind the materials path. Paths are relative to the assets folder unless absolute, as they are everywhere
+/// else in the crate.
+///
+/// This covers the files the crate probes and decodes itself: resolution negotiation, channel lookup and the maps
+/// merged or adjusted on the CPU. Maps handed to the asset server as they are still load through its asset sources.
+pub trait MaterialFileSystem: fmt::Debug + Send + Sync {
+    /// Whether a file or folder exists.
+    fn exists(&self, path: &Path) -> bool;
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    /// Names of the entries directly inside `folder`, in no particular order.
+    fn read_dir(&self, folder: &Path) -> io::Result<Vec<OsString>>;
+    /// Last modification time, used to tell whether a cached decode is stale. `None` disables caching for the file.
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+}
+
+/// The assets folder on disk, the default. Relative paths resolve like Bevy's default asset source, paths under
+/// `AmbientCGConfig::asset_root` arrive already resolved.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DiskFileSystem;
+
+impl MaterialFileSystem for DiskFileSystem {
+    fn exists(&self, path: &Path) -> bool {
+        default_resource_path(path).exists()
+    }
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(default_resource_path(path))
+    }
+    fn read_dir(&self, folder: &Path) -> io::Result<Vec<OsString>> {
+        std::fs::read_dir(default_resource_path(folder))?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect()
+    }
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(default_resource_path(path)).and_then(|metadata| metadata.modified()).ok()
+    }
+}
+
+/// A virtual material tree held in memory, e.g. to test loading without files on disk. Folders exist implicitly
+/// as soon as a file lives inside them.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryFileSystem {
+    files: HashMap<PathBuf, Vec<u8>>
+}
+
+impl InMemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a file at `path`, replacing any file already there.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, bytes: impl Into<Vec<u8>>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+    pub fn insert(&mut self, path: impl Into<PathBuf>, bytes: impl Into<Vec<u8>>) {
+        self.files.insert(path.into(), bytes.into());
+    }
+}
+
+impl MaterialFileSystem for InMemoryFileSystem {
+    fn exists(&self, path: &Path) -> bool {
+        self.files.keys().any(|file| file.starts_with(path))
+    }
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files.get(path).cloned().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+    fn read_dir(&self, folder: &Path) -> io::Result<Vec<OsString>> {
+        let mut entries: Vec<OsString> = self.files.keys()
+            .filter_map(|file| file.strip_prefix(folder).ok()?.components().next())
+            .map(|entry| entry.as_os_str().to_os_string())
+            .collect();
+        if entries.is_empty() {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        entries.sort_unstable();
+        entries.dedup();
+        Ok(entries)
+    }
+}
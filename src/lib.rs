@@ -6,13 +6,17 @@ This plugin allows you to easily import Ambient CG materials into Bevy with only
 
 Roughness/Metallic maps are automatically constructed with roughness data and metallic data going in the green and blue channels respectively of a generated map during runtime. No manual file conversions!
 
-As of now, only JPEG format images are implemented and will require enabling the bevy jpg feature.
+Each texture map is probed as `.jpg`, `.jpeg` and `.png` independently, so a material folder may mix formats (e.g. a PNG normal map next to a JPG color map). PNG maps require the bevy png feature, which is enabled by default.
 
-```
-cargo add bevy -F jpg
+Normal maps are loaded from `_NormalGL`, or from `_NormalDX` when a folder only ships the DirectX map, which is converted to OpenGL's convention on load. Set `normal_convention` on a material to pick one explicitly.
+
+```sh
+cargo add bevy -F jpeg
 ```
 
 ## Examples
+Runnable examples live in `examples/`: `cargo run --example switch_material` swaps materials on a keypress and `cargo run --example gallery` shows every material in `assets/materials`.
+
 Constructing an ambient CG material resource
 ```Rust
 pub const EXAMPLE_000: AmbientCGMaterial = AmbientCGMaterial {
@@ -21,7 +25,9 @@ pub const EXAMPLE_000: AmbientCGMaterial = AmbientCGMaterial {
     resolution: AmbientCGResolution::OneK,
     // this is the uv scale you want to render at, materials are generated to repeat
     // if uv_scale is None asset server will use default Affine value when loading
-    uv_scale: Some(Vec2::new(8., 8.))
+    uv_scale: Some(Vec2::new(8., 8.)),
+    // remaining options fall back to their defaults
+    ..AmbientCGMaterial::DEFAULT
 };
 
 pub const EXAMPLE_001: AmbientCGMaterial = AmbientCGMaterial {
@@ -31,6 +37,9 @@ pub const EXAMPLE_001: AmbientCGMaterial = AmbientCGMaterial {
     // This will allow you to selectively bundle textures and not have to determine resolution that is currently loaded if so desired
     resolution: AmbientCGResolution::SixteenK,
     uv_scale: None,
+    // flattens the normal map to half strength
+    normal_strength: Some(0.5),
+    ..AmbientCGMaterial::DEFAULT
 };
 ```
 ---
@@ -42,6 +51,20 @@ fn main() {
         .add_plugins(AmbientCGPlugin::default())
         .run()
 }
+
+fn main_with_config() {
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(AmbientCGPlugin {
+            config: AmbientCGConfig {
+                // tiling used by materials that leave uv_scale as None
+                default_uv_scale: Some(Vec2::splat(4.0)),
+                // merge large metallic-roughness maps over several frames instead of stalling one
+                merge_rows_per_frame: Some(256),
+                ..default()
+            }
+        })
+        .run()
+}
 ```
 ---
 Load a material and apply to mesh
@@ -50,12 +73,14 @@ fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
 ) {
     commands.spawn((
         PbrBundle {
             mesh: meshes.add(Cylinder::new(200.0, 0.1)),
-            material: EXAMPLE_000.load(&asset_server, &mut materials),
+            material: EXAMPLE_000.load(&asset_server, &mut materials, &config, &state),
             transform: Transform::from_xyz(0.0, -0.05, 0.0),
             ..default()
         },
@@ -66,60 +91,901 @@ fn setup(
         PbrBundle {
             mesh: meshes.add(Cylinder::new(200.0, 0.1)),
             // Here we define UV scale on the fly to override the value from defined AmbientCGMaterial
-            material: EXAMPLE_001.load_with_uv_scale(&asset_server, &mut materials, Vec2::(2.0, 2.0)),
+            material: EXAMPLE_001.load_with_uv_scale(&asset_server, &mut materials, Vec2::(2.0, 2.0), &config, &state),
             transform: Transform::from_xyz(0.0, -0.05, 0.0),
             ..default()
         },
     ));
-}``` */
+
+    // tile a 200 x 200 meter floor once per 2 meters, whatever its mesh size
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(200.0, 200.0))),
+        MeshMaterial3d(EXAMPLE_000.load_with_world_scale(&asset_server, &mut materials, Vec2::splat(200.0), 2.0, &config, &state)),
+    ));
+
+    // or repeat it 8 times across the longest side of a mesh sized at runtime
+    let wall = meshes.add(Cuboid::new(12.0, 3.0, 0.2));
+    let wall_material = EXAMPLE_000.load_fitted_to_mesh(&asset_server, &mut materials, &meshes, &wall, 8.0, &config, &state);
+    commands.spawn((Mesh3d(wall), MeshMaterial3d(wall_material)));
+
+    // load a themed set at the highest resolution all of its members have, so none looks sharper than the rest
+    let [wall, floor] = AmbientCGMaterial::load_matched(&[EXAMPLE_000, EXAMPLE_001], &asset_server, &mut materials, &config, &state)
+        .try_into()
+        .unwrap();
+
+    // on bevy versions with required components the material goes straight into a spawn tuple
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        EXAMPLE_000.load_component(&asset_server, &mut materials, &config, &state),
+    ));
+
+    // or spawn the mesh and material in one line
+    commands.spawn_ambient_cg(meshes.add(Cuboid::default()), &EXAMPLE_000, &asset_server, &mut materials, Transform::default(), &config, &state);
+}
+```
+---
+Loading into a custom material type
+```Rust
+#[derive(Asset, AsBindGroup, TypePath, Clone)]
+struct TerrainMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    albedo: Option<Handle<Image>>,
+}
+
+impl FromAmbientCGTextures for TerrainMaterial {
+    fn from_ambient_cg_textures(textures: MaterialTextures, _: &AmbientCGMaterial) -> Self {
+        TerrainMaterial { albedo: textures.base_color }
+    }
+}
+
+fn setup(
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    let terrain: Handle<TerrainMaterial> = EXAMPLE_000.load_as(&asset_server, &mut materials, &config, &state);
+}
+```
+---
+Overriding every material of a spawned scene
+```Rust
+fn spawn_scene(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    let material = EXAMPLE_000.load(&asset_server, &mut materials, &config, &state);
+    commands
+        .spawn(SceneRoot(asset_server.load("models/house.glb#Scene0")))
+        .observe(move |trigger: Trigger<SceneInstanceReady>, mut commands: Commands| {
+            commands.queue(apply_ambient_cg_material(trigger.target(), material.clone()));
+        });
+}
+```
+---
+Loading a material only once its mesh is first visible
+```Rust
+fn spawn_far_away(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        LazyAmbientCGMaterial(EXAMPLE_000),
+        Transform::from_xyz(0.0, 0.0, -500.0),
+    ));
+}
+```
+---
+Preloading materials during a loading state
+```Rust
+fn start_loading(mut commands: Commands) {
+    // one material is loaded per frame, AmbientCGPreloadProgress tracks how far along the queue is
+    commands.insert_resource(PreloadAmbientCGMaterials(vec![EXAMPLE_000, EXAMPLE_001]));
+}
+
+fn check_loading(progress: Res<AmbientCGPreloadProgress>, mut next_state: ResMut<NextState<GameState>>) {
+    if progress.is_finished() {
+        next_state.set(GameState::Playing);
+    }
+}
+```
+---
+Switching every loaded material to another resolution, e.g. from a graphics quality setting
+```Rust
+fn apply_quality(
+    registry: Res<AmbientCGRegistry>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    for (handle, error) in registry.reload_all(AmbientCGResolution::OneK, &asset_server, &mut materials, &config, &state) {
+        warn!("could not reload {:?}: {}", handle, error);
+    }
+}
+```
+---
+Inspecting what a material would load, without adding anything to `Assets`
+```Rust
+fn inspect(config: Res<AmbientCGConfig>) {
+    // scalars and texture sources of the StandardMaterial, serializable with the `serde` feature
+    let description = EXAMPLE_000.describe(&config).unwrap();
+    info!("{:?}", description.metallic_roughness_texture);
+}
+```
+---
+Copying loose textures into AmbientCG named folders, e.g. from a build script or a one-off tool
+```Rust
+fn main() -> std::io::Result<()> {
+    // legacy/brick_rough.png becomes assets/materials/Brick001_2K-JPG/Brick001_2K-JPG_Roughness.png
+    organize(Path::new("legacy"), Path::new("assets/materials"), |file| {
+        let channel = match file.file_stem()?.to_str()? {
+            "brick_albedo" => TextureChannel::Color,
+            "brick_rough" => TextureChannel::Roughness,
+            _ => return None
+        };
+        Some(OrganizedTexture { name: "Brick001".to_string(), resolution: AmbientCGResolution::TwoK, channel })
+    })
+}
+```
+---
+Embedding materials in the binary (requires the `embedded` feature)
+```Rust
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(AmbientCGPlugin::default())
+        // paths are relative to the assets folder and follow the usual material layout
+        .embed_ambient_cg_texture(
+            "materials/Example000_1K-JPG/Example000_1K-JPG_Color.jpg",
+            include_bytes!("../assets/materials/Example000_1K-JPG/Example000_1K-JPG_Color.jpg")
+        )
+        .run();
+}
+```
+---
+Exporting a material for other tools (requires the `export` feature)
+```Rust
+fn export(config: Res<AmbientCGConfig>) {
+    // writes the color and normal maps, a packed ORM texture and a glTF material snippet
+    let json_path = EXAMPLE_000.export_gltf(Path::new("export"), &config).unwrap();
+    info!("exported {}", json_path.display());
+}
+```
+---
+Loading an AmbientCG HDRI (requires the `hdri` feature)
+```Rust
+pub const SKY: AmbientCGEnvironment = AmbientCGEnvironment {
+    // found at assets/materials/DayEnvironmentHDRI019_2K-HDR.exr, or a smaller resolution
+    name: "DayEnvironmentHDRI019",
+    resolution: AmbientCGResolution::TwoK,
+    subfolder: None,
+};
+
+fn load_sky(asset_server: Res<AssetServer>, config: Res<AmbientCGConfig>) {
+    // an equirectangular image, reproject it to a cubemap for Skybox or EnvironmentMapLight
+    let sky: Handle<Image> = SKY.load_environment(&asset_server, &config);
+}
+```
+---
+Declaring materials as `bevy_asset_loader` dynamic assets (requires the `bevy_asset_loader` feature)
+```Rust
+// assets/materials.ambientcg.ron
+// ({
+//     "rock": (name: "Rock023", resolution: Some("2K")),
+// })
+
+#[derive(AssetCollection, Resource)]
+struct MaterialAssets {
+    #[asset(key = "rock")]
+    rock: Handle<StandardMaterial>,
+}
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, AmbientCGPlugin::default()))
+        .add_plugins(RonAssetPlugin::<AmbientCGDynamicAssets>::new(&["ambientcg.ron"]))
+        .init_state::<GameState>()
+        .add_loading_state(
+            LoadingState::new(GameState::Loading)
+                .continue_to_state(GameState::Playing)
+                .register_dynamic_asset_collection::<AmbientCGDynamicAssets>()
+                .with_dynamic_assets_file::<AmbientCGDynamicAssets>("materials.ambientcg.ron")
+                .load_collection::<MaterialAssets>()
+        )
+        .run();
+}
+``` */
+
+mod file_system;
+pub use file_system::*;
+mod organize;
+pub use organize::*;
+#[cfg(feature = "bevy_asset_loader")]
+mod asset_loader;
+#[cfg(feature = "bevy_asset_loader")]
+pub use asset_loader::*;
+#[cfg(feature = "hdri")]
+mod environment;
+#[cfg(feature = "hdri")]
+pub use environment::*;
 
 use core::fmt;
 use std::ffi::OsStr;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::error::Error;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::sync::{LazyLock, Mutex};
+use std::sync::{Arc, Mutex};
 
 use bevy::asset::io::file::FileAssetReader;
+#[cfg(feature = "embedded")]
+use bevy::asset::io::embedded::EmbeddedAssetRegistry;
+#[cfg(feature = "embedded")]
+use bevy::asset::AssetPath;
 use bevy::math::Affine2;
 use bevy::prelude::*;
+use bevy::render::mesh::{MeshAabb, VertexAttributeValues};
 use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::Face;
+use bevy::tasks::AsyncComputeTaskPool;
 use bevy::image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor};
-use image::{DynamicImage, GenericImageView, ImageReader, RgbImage};
+use image::{DynamicImage, GenericImageView, GrayImage, ImageBuffer, ImageReader, Luma, RgbImage, RgbaImage};
+
+/// Per channel strings of a descriptor, e.g. `channel_subfolders`, leaked for registered descriptors.
+type ChannelStrs = &'static [(TextureChannel, &'static str)];
 
+#[derive(Default)]
 pub struct AmbientCGPlugin {
     pub config: AmbientCGConfig
 }
 
-static MATERIALS_PATH: LazyLock<Mutex<String>> = LazyLock::new(|| Mutex::new("materials".to_string()));
-static RESOLUTION_NEGOTIATION: LazyLock<Mutex<bool>> = LazyLock::new(|| Mutex::new(true));
+/// Bookkeeping of the material loads of one App, inserted as a resource by `AmbientCGPlugin` and passed to every load
+/// next to `AmbientCGConfig`. Clones share the same state, so `load_async` can carry it into its task.
+#[derive(Resource, Clone, Default)]
+pub struct AmbientCGState(Arc<LoadState>);
 
-impl Default for AmbientCGPlugin {
-    fn default() -> Self {
-        Self {
-            config: AmbientCGConfig {
-                materials_path: MATERIALS_PATH.lock().unwrap().to_owned(),
-                resolution_negotiation: *RESOLUTION_NEGOTIATION.lock().unwrap()}
-        }
-    }
+#[derive(Default)]
+struct LoadState {
+    // loads can't reach the registry resource, so they queue up here until sync_ambient_cg_registry moves them over
+    registrations: Mutex<Vec<AmbientCGRegistryEntry>>,
+    summary: Mutex<AmbientCGLoadSummary>,
+    merges: Mutex<Vec<MergeJob>>,
+    loaded_materials: Mutex<HashMap<LoadedMaterialKey, AssetId<StandardMaterial>>>,
+    grayscale_cache: Mutex<GrayscaleCache>,
+    // registered descriptors need 'static channel strings, so each distinct set is leaked once and reused afterwards
+    channel_strs: Mutex<Vec<ChannelStrs>>,
+    // texture descriptor labels are 'static, so each distinct label is leaked once and reused afterwards
+    image_labels: Mutex<HashSet<&'static str>>
 }
 
 impl Plugin for AmbientCGPlugin {
     fn build(&self, app: &mut App) {
-        *MATERIALS_PATH.lock().unwrap() = self.config.materials_path.to_owned();
-        *RESOLUTION_NEGOTIATION.lock().unwrap() = self.config.resolution_negotiation;
         app
-            .insert_resource::<AmbientCGConfig>(self.config.to_owned());
+            .insert_resource::<AmbientCGConfig>(self.config.to_owned())
+            .init_resource::<AmbientCGState>()
+            .init_resource::<AmbientCGPreloadProgress>()
+            .register_type::<AmbientCGRegistry>()
+            .init_resource::<AmbientCGRegistry>()
+            .init_resource::<AmbientCGLoadSummary>()
+            .add_systems(Startup, (check_materials_path, check_image_formats))
+            .add_systems(PostStartup, (sync_ambient_cg_registry, log_ambient_cg_load_summary).chain())
+            .add_systems(First, (forget_reused_materials.run_if(resource_changed::<AmbientCGConfig>), sync_ambient_cg_registry))
+            .add_systems(Update, advance_ambient_cg_merges)
+            .add_systems(Update, preload_ambient_cg_materials.run_if(bevy::prelude::resource_exists::<PreloadAmbientCGMaterials>))
+            .add_systems(Update, load_visible_ambient_cg_materials.run_if(any_with_component::<LazyAmbientCGMaterial>));
+        #[cfg(feature = "bevy_asset_loader")]
+        app.add_systems(
+            PreUpdate,
+            asset_loader::load_ambient_cg_dynamic_assets
+                .after(bevy::asset::TrackAssets)
+                .run_if(bevy::prelude::resource_exists::<Assets<AmbientCGDynamicAssets>>)
+        );
+    }
+}
+
+/// Reports a misconfigured `materials_path` at startup instead of at the first silently empty material.
+fn check_materials_path(config: Res<AmbientCGConfig>) {
+    if !config.headless && !resource_exists(&config.materials_path, &config) {
+        error!(
+            "AmbientCG materials path \"{}\" does not exist, expected a folder at {}",
+            config.materials_path.display(),
+            resolve_resource_path(&config.materials_path, config.asset_root.as_deref()).display()
+        );
+    }
+}
+
+/// Registers material textures compiled into the binary so they can be loaded without an assets folder.
+#[cfg(feature = "embedded")]
+pub trait AmbientCGEmbedExt {
+    /// Embeds a single texture under `path`, relative to the assets folder, into the `AmbientCGConfig` resource, so
+    /// call it after adding `AmbientCGPlugin`.
+    ///
+    /// The path must follow the same layout `AmbientCGMaterial` resolves on disk, e.g.
+    /// `materials/Example000_1K-JPG/Example000_1K-JPG_Color.jpg`.
+    fn embed_ambient_cg_texture(&mut self, path: impl Into<PathBuf>, bytes: &'static [u8]) -> &mut Self;
+}
+
+#[cfg(feature = "embedded")]
+impl AmbientCGEmbedExt for App {
+    fn embed_ambient_cg_texture(&mut self, path: impl Into<PathBuf>, bytes: &'static [u8]) -> &mut Self {
+        let path = path.into();
+        self.world()
+            .resource::<EmbeddedAssetRegistry>()
+            .insert_asset(path.clone(), &path, bytes);
+        self.world_mut().resource_mut::<AmbientCGConfig>().embedded_textures.insert(path, bytes);
+        self
     }
 }
 
+/// Inserted as a resource by `AmbientCGPlugin` and passed to every load, e.g. `AmbientCGMaterial::load` takes it as
+/// `Res<AmbientCGConfig>`. Changes to the resource apply to the next load, a new `materials_path` is searched by
+/// every material loaded after it.
 #[derive(Clone, Debug, Resource)]
 pub struct AmbientCGConfig {
-    pub materials_path: String,
-    pub resolution_negotiation: bool
+    /// The assets folder `materials_path` is resolved against when probing and reading files. `None` resolves it like
+    /// Bevy's default asset source, `assets` under `BEVY_ASSET_ROOT`, the crate root under cargo or the executable's
+    /// folder. Textures are still loaded through the asset server, so set `AssetPlugin::file_path` to the same folder,
+    /// e.g. `CARGO_MANIFEST_DIR/tests/assets` for fixture based tests.
+    pub asset_root: Option<PathBuf>,
+    pub materials_path: PathBuf,
+    /// Steps down to the next smaller resolution on disk when the requested one is missing. Ignored when
+    /// `resolution_policy` is `Exact`.
+    pub resolution_negotiation: bool,
+    pub resolution_policy: AmbientCGResolutionPolicy,
+    /// Strategy picking the resolution under `AmbientCGResolutionPolicy::Negotiate`, `StepDownNegotiator` by default.
+    pub resolution_negotiator: Arc<dyn ResolutionNegotiator>,
+    /// Skips all file reads and texture creation for builds without a render world, such as dedicated servers.
+    /// Loading returns a default handle.
+    pub headless: bool,
+    /// UV scale used by `load` for materials whose own `uv_scale` is `None`.
+    pub default_uv_scale: Option<Vec2>,
+    /// Packs the `_AmbientOcclusion` map into the red channel of the generated metallic-roughness texture and samples
+    /// occlusion from that same texture, saving a texture binding. Only applies when the merge runs, i.e. both
+    /// `_Metalness` and `_Roughness` maps exist.
+    pub pack_occlusion: bool,
+    /// Flips the V coordinate of every material, for meshes authored with the opposite V convention. The flip is
+    /// applied to the mesh UVs before `uv_scale`, and the normal map's Y (green) channel is flipped to match.
+    pub flip_v: bool,
+    /// Called after every row of the metallic-roughness merge with the rows done and the total row count, e.g. to
+    /// drive a loading bar while large textures are merged. The merge runs on the thread that loads the material.
+    pub merge_progress: Option<fn(done: u32, total: u32)>,
+    /// Lowest mip level sampled by every material texture, raise it to keep distant tiling sharper. wgpu samplers
+    /// have no LOD bias, so clamping is the available lever.
+    pub lod_min_clamp: f32,
+    /// Highest mip level sampled by every material texture, lower it to keep grazing angles from blurring out.
+    pub lod_max_clamp: f32,
+    /// Which map is packed into each channel of the generated metallic-roughness texture. The default is the glTF
+    /// layout bevy samples from, change it only when the texture feeds a custom shader.
+    pub channel_layout: ChannelLayout,
+    /// How every material texture tiles. `MirrorRepeat` hides seams of materials that aren't perfectly tileable at the
+    /// cost of mirrored tiles.
+    pub address_mode: ImageAddressMode,
+    /// Samplers replacing the default one for single channels, e.g. `ImageSamplerDescriptor::linear()` for `Color`
+    /// while `Displacement` keeps crisp nearest steps. They are used as is, so `address_mode` and the LOD clamps
+    /// don't apply to them. The generated metallic-roughness texture takes the `Metalness` sampler and the flat
+    /// normal fallback the `NormalGL` one.
+    pub channel_samplers: HashMap<TextureChannel, ImageSamplerDescriptor>,
+    /// Which channel of a map holds its data when the map is reduced to grayscale on the CPU, i.e. merged into the
+    /// metallic-roughness texture or attenuated, for packs that store e.g. roughness in the green channel only.
+    /// Channels without an entry read luma.
+    pub grayscale_sources: HashMap<TextureChannel, GrayscaleSource>,
+    /// Channels every material must have after resolution negotiation, loading fails with `MissingChannel` when one
+    /// is absent. Empty by default, which loads whatever maps exist.
+    pub required_channels: Vec<TextureChannel>,
+    /// Gives materials without a `_NormalGL` map a flat 1x1 normal map, so `normal_map_texture` is always set and
+    /// every material uses the same shader permutation.
+    pub flat_normal_fallback: bool,
+    /// Spreads the metallic-roughness merge over frames, merging this many rows per frame, to avoid a frame spike on
+    /// large textures. Materials sample a neutral placeholder until their merge finishes. `None` merges at load time.
+    pub merge_rows_per_frame: Option<usize>,
+    /// Downsamples the full mip chain of the generated metallic-roughness texture on the CPU, bilinearly level by
+    /// level, for hardware where the GPU can't generate mips at runtime and the merged map aliases in the distance.
+    /// Pair it with a `Metalness` entry in `channel_samplers` using a linear `mipmap_filter` to blend between levels.
+    pub merged_mip_chain: bool,
+    /// Downsamples every map larger than this many pixels on its longest side on the CPU at load time, keeping its
+    /// aspect ratio, to cap VRAM when only large resolutions are bundled. Maps are read on the CPU to check their size,
+    /// and generated images, e.g. the merged metallic-roughness map, are built from the downsampled maps.
+    pub max_texture_size: Option<u32>,
+    /// Where images synthesized on the CPU, e.g. the merged metallic-roughness map, are kept. Drop
+    /// `RenderAssetUsages::MAIN_WORLD` to free their CPU buffers once uploaded to the GPU, which saves memory on the
+    /// web. They render the same, but can no longer be read back from `Assets<Image>` after the upload.
+    pub generated_image_usage: RenderAssetUsages,
+    /// Resolution of materials built at runtime without one, by `AmbientCGMaterial::with_name`, bare asset IDs and
+    /// dynamic assets. Const materials always name their resolution.
+    pub default_resolution: AmbientCGResolution,
+    /// Called with the absolute path and the result of every existence check the loader makes, e.g. so a test can
+    /// assert which files a material probed for. Directory listings report the folder, then each entry they matched
+    /// or the path they found no match for.
+    pub on_file_probe: Option<fn(&Path, bool)>,
+    /// Loads materials without the maps that fail to decode, logging a warning, instead of failing the whole load.
+    /// When one of metalness and roughness fails the other still drives its channel, and maps merged with a roughness
+    /// map of another size are resized to it. This covers maps decoded on the CPU, i.e. merged, adjusted or supplied
+    /// as bytes; textures the asset server loads fail on their own.
+    pub lenient_decode: bool,
+    /// What to do when the channels found for a material differ in pixel size or sit outside its negotiated
+    /// `{name}_{resolution}-JPG` folder, e.g. a 4K `_Color` copied into a 1K folder from a mixed download.
+    /// Checking reads every found map.
+    pub mixed_resolutions: MixedResolutions,
+    /// Returns the existing handle when a material is loaded again with the same fields, materials path and UV
+    /// transform while its first handle is alive, instead of loading its textures into a new `StandardMaterial`.
+    /// Loads that differ only in `uv_scale` get separate materials. Changing the config resource starts over.
+    pub reuse_materials: bool,
+    /// Where material files are probed and read from, the assets folder on disk by default. Swap in an
+    /// `InMemoryFileSystem` to load a virtual material tree, e.g. in tests.
+    pub file_system: Arc<dyn MaterialFileSystem>,
+    /// Textures compiled into the binary, checked before `file_system`. Filled by
+    /// `AmbientCGEmbedExt::embed_ambient_cg_texture`, which also hands them to the asset server.
+    #[cfg(feature = "embedded")]
+    pub embedded_textures: HashMap<PathBuf, &'static [u8]>
+}
+
+/// Handling of materials whose maps don't share one resolution, see `AmbientCGConfig::mixed_resolutions`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MixedResolutions {
+    /// Loads the maps as found without checking.
+    #[default]
+    Allow,
+    /// Logs a warning naming the first mismatched map and loads the material anyway.
+    Warn,
+    /// Fails the load with `AmbientCGErrorType::MixedResolution`.
+    Deny,
+}
+
+/// What a map is reduced to when decoded as grayscale, see `AmbientCGConfig::grayscale_sources`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GrayscaleSource {
+    /// The luminance of the color channels.
+    #[default]
+    Luma,
+    Red,
+    Green,
+    Blue,
+}
+
+/// A map that can be packed into a channel of the generated metallic-roughness texture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackedSource {
+    /// The `_AmbientOcclusion` map when `pack_occlusion` is enabled, black otherwise.
+    Occlusion,
+    Roughness,
+    Metalness,
+    Zero,
+}
+
+/// Assignment of maps to the red, green and blue channels of the generated metallic-roughness texture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelLayout {
+    pub red: PackedSource,
+    pub green: PackedSource,
+    pub blue: PackedSource
+}
+
+impl ChannelLayout {
+    /// glTF layout sampled by `StandardMaterial`, occlusion in red, roughness in green and metalness in blue.
+    pub const GLTF: Self = Self {
+        red: PackedSource::Occlusion,
+        green: PackedSource::Roughness,
+        blue: PackedSource::Metalness
+    };
+}
+
+impl Default for ChannelLayout {
+    fn default() -> Self {
+        Self::GLTF
+    }
+}
+
+/// How a material's requested resolution is matched against the folders on disk.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AmbientCGResolutionPolicy {
+    /// Falls back to smaller resolutions if `resolution_negotiation` is enabled.
+    #[default]
+    Negotiate,
+    /// Only loads the requested resolution and fails with `MissingFiles` if its folder is missing, so the loaded
+    /// resolution always equals the requested one.
+    Exact,
+}
+
+/// Picks the resolution a material loads at under `AmbientCGResolutionPolicy::Negotiate`, see
+/// `AmbientCGConfig::resolution_negotiator`. `AmbientCGMaterial::available_resolutions` lists the folders on disk
+/// through `config.file_system`.
+pub trait ResolutionNegotiator: fmt::Debug + Send + Sync {
+    fn negotiate(&self, material: &AmbientCGMaterial<'_>, config: &AmbientCGConfig) -> Result<AmbientCGResolution, AmbientCGImportError>;
 }
 
-#[derive(Clone, Default)]
+/// Steps down to the largest resolution on disk that isn't above the requested one, the default negotiator.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StepDownNegotiator;
+
+impl ResolutionNegotiator for StepDownNegotiator {
+    fn negotiate(&self, material: &AmbientCGMaterial<'_>, config: &AmbientCGConfig) -> Result<AmbientCGResolution, AmbientCGImportError> {
+        material.available_resolutions(config)
+            .into_iter()
+            .find(|available| *available <= material.resolution)
+            .ok_or_else(|| material.missing_files(config))
+    }
+}
+
+impl Default for AmbientCGConfig {
+    fn default() -> Self {
+        Self {
+            asset_root: None,
+            materials_path: PathBuf::from("materials"),
+            resolution_negotiation: true,
+            resolution_policy: AmbientCGResolutionPolicy::Negotiate,
+            resolution_negotiator: Arc::new(StepDownNegotiator),
+            headless: false,
+            default_uv_scale: None,
+            pack_occlusion: false,
+            flip_v: false,
+            merge_progress: None,
+            lod_min_clamp: ImageSamplerDescriptor::default().lod_min_clamp,
+            lod_max_clamp: ImageSamplerDescriptor::default().lod_max_clamp,
+            channel_layout: ChannelLayout::GLTF,
+            address_mode: ImageAddressMode::Repeat,
+            channel_samplers: HashMap::new(),
+            grayscale_sources: HashMap::new(),
+            required_channels: Vec::new(),
+            flat_normal_fallback: false,
+            merge_rows_per_frame: None,
+            merged_mip_chain: false,
+            max_texture_size: None,
+            generated_image_usage: RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
+            default_resolution: AmbientCGResolution::default(),
+            on_file_probe: None,
+            lenient_decode: false,
+            mixed_resolutions: MixedResolutions::Allow,
+            reuse_materials: false,
+            file_system: Arc::new(DiskFileSystem),
+            #[cfg(feature = "embedded")]
+            embedded_textures: HashMap::new()
+        }
+    }
+}
+
+impl AmbientCGConfig {
+    /// `p` as it is handed to `file_system`, resolved against `asset_root` when one is set. Without one relative paths
+    /// are passed on as they are, `DiskFileSystem` resolves them like Bevy's default asset source.
+    fn file_path(&self, p: &Path) -> PathBuf {
+        match &self.asset_root {
+            Some(asset_root) => resolve_resource_path(p, Some(asset_root)),
+            None => p.to_path_buf()
+        }
+    }
+    /// Scans `materials_path` recursively for material folders named `{name}_{resolution}-{variant}` and returns the
+    /// name, resolution and variant of each, sorted by name. The `.cache` folder is skipped.
+    pub fn discover(&self) -> Vec<(String, AmbientCGResolution, AmbientCGVariant)> {
+        let mut discovered = Vec::new();
+        discover_materials(&self.materials_path, Path::new(""), self, &mut discovered);
+        discovered.sort_by(|a, b| a.0.cmp(&b.0));
+        discovered.into_iter().map(|(name, resolution, variant, _)| (name, resolution, variant)).collect()
+    }
+    /// Lists which channels every material found by `discover` has, sorted, e.g. to fail a CI job when a material
+    /// ships without a color or normal map. Serializable with the `serde` feature.
+    pub fn audit(&self) -> Vec<MaterialAudit> {
+        let mut discovered = Vec::new();
+        discover_materials(&self.materials_path, Path::new(""), self, &mut discovered);
+        let mut audits: Vec<MaterialAudit> = discovered
+            .into_iter()
+            .filter_map(|(name, resolution, variant, subfolder)| {
+                let material = AmbientCGMaterial {
+                    name: &name,
+                    resolution,
+                    variant,
+                    subfolder: Some(subfolder.to_str()?).filter(|subfolder| !subfolder.is_empty()),
+                    ..AmbientCGMaterial::DEFAULT
+                };
+                let paths = material.texture_paths(self);
+                let channels = TextureChannel::ALL.into_iter().filter(|channel| find_texture(paths.get(*channel), self).is_some()).collect();
+                Some(MaterialAudit { name: name.clone(), resolution, variant, subfolder: subfolder.clone(), channels })
+            })
+            .collect();
+        audits.sort();
+        audits
+    }
+}
+
+/// The channels found for one material folder, see `AmbientCGConfig::audit`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MaterialAudit {
+    pub name: String,
+    pub resolution: AmbientCGResolution,
+    pub variant: AmbientCGVariant,
+    /// Folder holding the material folder, relative to `materials_path` and empty at its root.
+    pub subfolder: PathBuf,
+    /// Channels with a file, in the order of `TextureChannel::ALL`.
+    pub channels: Vec<TextureChannel>
+}
+
+impl MaterialAudit {
+    /// Whether the material has a color and a normal map, which a useful `StandardMaterial` needs.
+    pub fn is_complete(&self) -> bool {
+        self.channels.contains(&TextureChannel::Color)
+            && (self.channels.contains(&TextureChannel::NormalGL) || self.channels.contains(&TextureChannel::NormalDX))
+    }
+    /// Channels without a file, in the order of `TextureChannel::ALL`.
+    pub fn missing(&self) -> Vec<TextureChannel> {
+        TextureChannel::ALL.into_iter().filter(|channel| !self.channels.contains(channel)).collect()
+    }
+}
+
+/// Queue of materials to load over several frames, e.g. while a loading state is active.
+///
+/// One material is loaded per frame so the metallic-roughness merges don't all land in a single frame. The
+/// resource removes itself once the queue is empty, track completion through `AmbientCGPreloadProgress`.
+#[derive(Default, Resource)]
+pub struct PreloadAmbientCGMaterials(pub Vec<AmbientCGMaterial<'static>>);
+
+/// Progress of the materials queued through `PreloadAmbientCGMaterials`.
+#[derive(Default, Resource)]
+pub struct AmbientCGPreloadProgress {
+    /// Handles of every preloaded material, in the order they were queued. Materials that fail to load log an error
+    /// and get no handle.
+    pub handles: Vec<Handle<StandardMaterial>>,
+    /// Number of queued materials that have not been loaded yet.
+    pub remaining: usize
+}
+
+impl AmbientCGPreloadProgress {
+    /// Fraction of the queued materials that have been loaded, from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        let total = self.handles.len() + self.remaining;
+        if total == 0 {
+            return 1.0;
+        }
+        self.handles.len() as f32 / total as f32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Loads the next queued material from `PreloadAmbientCGMaterials`, order your own systems after this one when
+/// checking `AmbientCGPreloadProgress` in the same frame the queue is inserted.
+pub fn preload_ambient_cg_materials(
+    mut commands: Commands,
+    mut preload: ResMut<PreloadAmbientCGMaterials>,
+    mut progress: ResMut<AmbientCGPreloadProgress>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    if !preload.0.is_empty() {
+        let material = preload.0.remove(0);
+        match material.try_load(&asset_server, &mut materials, &config, &state) {
+            Ok(handle) => progress.handles.push(handle),
+            Err(error) => error!("Preloaded AmbientCG material {} failed to load: {}", material.name, error)
+        }
+    }
+    progress.remaining = preload.0.len();
+    if preload.0.is_empty() {
+        commands.remove_resource::<PreloadAmbientCGMaterials>();
+    }
+}
+
+/// A material loaded only once its entity is first visible, e.g. to keep the textures of offscreen parts of a large
+/// world out of memory. Spawn it next to a `Mesh3d`, `load_visible_ambient_cg_materials` replaces it with the
+/// loaded `MeshMaterial3d`.
+#[derive(Component, Clone, Debug)]
+pub struct LazyAmbientCGMaterial(pub AmbientCGMaterial<'static>);
+
+/// Loads the `LazyAmbientCGMaterial` of every entity that was visible in a view last frame. A material that fails
+/// to load is logged and dropped, leaving the entity without a material.
+pub fn load_visible_ambient_cg_materials(
+    mut commands: Commands,
+    lazy_materials: Query<(Entity, &LazyAmbientCGMaterial, &ViewVisibility)>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AmbientCGConfig>,
+    state: Res<AmbientCGState>
+) {
+    for (entity, lazy, visibility) in &lazy_materials {
+        if !visibility.get() {
+            continue;
+        }
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<LazyAmbientCGMaterial>();
+        match lazy.0.try_load(&asset_server, &mut materials, &config, &state) {
+            Ok(handle) => {
+                entity_commands.insert(MeshMaterial3d(handle));
+            }
+            Err(error) => error!("Lazy AmbientCG material {} failed to load: {}", lazy.0.name, error)
+        }
+    }
+}
+
+/// Every AmbientCG material loaded this session, registered for reflection so inspectors can list it.
+/// Entries are added at the start of the frame after their material was loaded.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct AmbientCGRegistry {
+    pub entries: Vec<AmbientCGRegistryEntry>
+}
+
+impl AmbientCGRegistry {
+    pub fn get(&self, handle: &Handle<StandardMaterial>) -> Option<&AmbientCGRegistryEntry> {
+        self.entries.iter().find(|entry| entry.handle.id() == handle.id())
+    }
+
+    /// Forgets a material, pair it with `Assets::remove` to evict the material itself.
+    pub fn remove(&mut self, handle: &Handle<StandardMaterial>) -> Option<AmbientCGRegistryEntry> {
+        let index = self.entries.iter().position(|entry| entry.handle.id() == handle.id())?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Reloads every registered material at `resolution` into its existing handle with `load_into`, e.g. when a
+    /// graphics quality setting changes. Resolution is negotiated per material as usual, so materials only shipped
+    /// in smaller sizes step down. Materials that fail keep their current textures and are returned with the error.
+    ///
+    /// The registry picks up the newly loaded resolutions at the start of the next frame.
+    pub fn reload_all(
+        &self,
+        resolution: AmbientCGResolution,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Vec<(Handle<StandardMaterial>, AmbientCGImportError)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                AmbientCGMaterial { resolution, ..entry.material() }
+                    .load_into(&entry.handle, asset_server, materials, config, state)
+                    .err()
+                    .map(|error| (entry.handle.clone(), error))
+            })
+            .collect()
+    }
+}
+
+/// A loaded material along with the descriptor it was loaded from.
+#[derive(Clone, Reflect)]
+pub struct AmbientCGRegistryEntry {
+    pub name: String,
+    pub subfolder: Option<String>,
+    /// Resolution that was actually loaded, after negotiation.
+    pub resolution: AmbientCGResolution,
+    pub handle: Handle<StandardMaterial>,
+    #[reflect(ignore)]
+    descriptor: AmbientCGMaterial<'static>
+}
+
+impl AmbientCGRegistryEntry {
+    /// Rebuilds the descriptor this material was loaded from, e.g. to load it again.
+    pub fn material(&self) -> AmbientCGMaterial<'_> {
+        AmbientCGMaterial {
+            name: &self.name,
+            subfolder: self.subfolder.as_deref(),
+            ..self.descriptor.clone()
+        }
+    }
+}
+
+/// Reports variants bevy can't load textures of, e.g. JPGs without its `jpeg` feature. The maps would otherwise go
+/// missing silently while the metallic-roughness merge, decoded by the `image` crate, still works.
+fn check_image_formats(config: Res<AmbientCGConfig>) {
+    if config.headless {
+        return;
+    }
+    for (variant, feature) in missing_image_features() {
+        let message = format!(
+            "bevy can't load .{} textures, so {} materials will miss their maps. Enable bevy's `{}` feature, e.g. `cargo add bevy -F {}`",
+            variant.extension(),
+            variant,
+            feature,
+            feature
+        );
+        // JPG is the default variant, PNG only matters to materials that ship it
+        match variant {
+            AmbientCGVariant::Jpg => error!("{}", message),
+            AmbientCGVariant::Png | AmbientCGVariant::Hdr => warn!("{}", message)
+        }
+    }
+}
+
+/// Variants whose extension bevy's image loader wasn't built for, with the bevy feature adding it.
+fn missing_image_features() -> Vec<(AmbientCGVariant, &'static str)> {
+    [(AmbientCGVariant::Jpg, "jpeg"), (AmbientCGVariant::Png, "png")]
+        .into_iter()
+        .filter(|(variant, _)| bevy::image::ImageFormat::from_extension(variant.extension()).is_none())
+        .collect()
+}
+
+/// Forgets the materials kept for `AmbientCGConfig::reuse_materials` when the config changes, materials loaded under
+/// the old config may not match what it loads now.
+fn forget_reused_materials(config: Res<AmbientCGConfig>, state: Res<AmbientCGState>) {
+    if !config.is_added() {
+        state.0.loaded_materials.lock().unwrap().clear();
+    }
+}
+
+/// What makes two loads interchangeable under `AmbientCGConfig::reuse_materials`. The UV transform is compared
+/// rather than `uv_scale`, which `load_with_uv_scale` and friends override.
+#[derive(PartialEq, Eq, Hash)]
+struct LoadedMaterialKey {
+    name: String,
+    subfolder: Option<String>,
+    materials_path: PathBuf,
+    descriptor: AmbientCGMaterial<'static>,
+    uv_transform: [u32; 6]
+}
+
+/// Moves materials loaded since the last frame into `AmbientCGRegistry` and `AmbientCGLoadSummary`, replacing
+/// entries of reused handles.
+fn sync_ambient_cg_registry(
+    mut registry: ResMut<AmbientCGRegistry>,
+    mut summary: ResMut<AmbientCGLoadSummary>,
+    state: Res<AmbientCGState>
+) {
+    for entry in state.0.registrations.lock().unwrap().drain(..) {
+        registry.remove(&entry.handle);
+        registry.entries.push(entry);
+    }
+    let pending = std::mem::take(&mut *state.0.summary.lock().unwrap());
+    summary.loaded += pending.loaded;
+    summary.fell_back += pending.fell_back;
+    summary.failed += pending.failed;
+}
+
+/// Running count of material loads, logged once after `Startup` and kept up to date every frame afterwards.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AmbientCGLoadSummary {
+    /// Materials loaded successfully, including those that fell back.
+    pub loaded: usize,
+    /// Materials loaded at a smaller resolution than requested.
+    pub fell_back: usize,
+    /// Materials that failed to load, e.g. because their folder is missing.
+    pub failed: usize
+}
+
+impl AmbientCGLoadSummary {
+    /// Counts one load of a material requested at `requested`, `loaded` is `None` when it failed.
+    fn record(&mut self, requested: AmbientCGResolution, loaded: Option<AmbientCGResolution>) {
+        match loaded {
+            Some(loaded) => {
+                self.loaded += 1;
+                if loaded < requested {
+                    self.fell_back += 1;
+                }
+            }
+            None => self.failed += 1
+        }
+    }
+}
+
+impl fmt::Display for AmbientCGLoadSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Loaded {} AmbientCG materials, {} fell back in resolution, {} failed",
+            self.loaded, self.fell_back, self.failed
+        )
+    }
+}
+
+fn log_ambient_cg_load_summary(summary: Res<AmbientCGLoadSummary>, config: Res<AmbientCGConfig>) {
+    if config.headless {
+        return;
+    }
+    if summary.failed > 0 {
+        warn!("{}", *summary);
+    } else {
+        info!("{}", *summary);
+    }
+}
+
+/// Variants are ordered from smallest to largest, so resolutions compare by size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AmbientCGResolution {
     #[default]
     OneK,
@@ -131,6 +997,25 @@ pub enum AmbientCGResolution {
 }
 
 impl AmbientCGResolution {
+    /// Approximate edge length in pixels, e.g. 2048 for 2K.
+    pub fn width_px(&self) -> u32 {
+        match self {
+            Self::OneK => 1024,
+            Self::TwoK => 2048,
+            Self::FourK => 4096,
+            Self::EightK => 8192,
+            Self::TwelveK => 12288,
+            Self::SixteenK => 16384,
+        }
+    }
+    /// The standard resolution nearest to an edge length of `px`, rounding up halfway between two sizes.
+    pub fn from_px(px: u32) -> Self {
+        [Self::OneK, Self::TwoK, Self::FourK, Self::EightK, Self::TwelveK, Self::SixteenK]
+            .into_iter()
+            .rev()
+            .min_by_key(|resolution| resolution.width_px().abs_diff(px))
+            .unwrap()
+    }
     pub fn next_smaller(&self) -> Result<Self, AmbientCGImportError> {
         match &self {
             Self::OneK => Err(AmbientCGImportError(AmbientCGErrorType::NotFound)),
@@ -143,6 +1028,22 @@ impl AmbientCGResolution {
     }
 }
 
+impl FromStr for AmbientCGResolution {
+    type Err = AmbientCGImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1K" => Ok(Self::OneK),
+            "2K" => Ok(Self::TwoK),
+            "4K" => Ok(Self::FourK),
+            "8K" => Ok(Self::EightK),
+            "12K" => Ok(Self::TwelveK),
+            "16K" => Ok(Self::SixteenK),
+            _ => Err(AmbientCGImportError(AmbientCGErrorType::InvalidResolution))
+        }
+    }
+}
+
 impl std::fmt::Display for AmbientCGResolution {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match *self {
@@ -157,202 +1058,3152 @@ impl std::fmt::Display for AmbientCGResolution {
     }
 }
 
-#[derive(Debug)]
-pub struct AmbientCGImportError(AmbientCGErrorType);
-
-#[derive(Debug)]
-enum AmbientCGErrorType {
-    NotFound,
+/// The download variant of an AmbientCG material, which names both its folder and its file prefix,
+/// e.g. `Rock023_2K-JPG` or `Rock023_2K-PNG`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AmbientCGVariant {
+    #[default]
+    Jpg,
+    Png,
+    /// A single HDRI file such as `DayEnvironmentHDRI019_2K-HDR.exr` instead of a folder of maps, loaded through
+    /// `AmbientCGEnvironment`.
+    Hdr,
 }
 
-impl fmt::Display for AmbientCGImportError {
+impl std::fmt::Display for AmbientCGVariant {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", &self)
+        let str = match *self {
+            Self::Jpg => "JPG",
+            Self::Png => "PNG",
+            Self::Hdr => "HDR",
+        };
+        write!(f, "{}", str)
     }
 }
 
-impl Error for AmbientCGImportError {
-    fn description(&self) -> &str {
-        match self.0 {
-            AmbientCGErrorType::NotFound => "Material not found in assets folder"
+impl AmbientCGVariant {
+    /// File extension of the variant's texture files.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jpg => "jpg",
+            Self::Png => "png",
+            Self::Hdr => "exr",
         }
     }
+    /// Splits the `-JPG` or `-PNG` suffix off a download name, e.g. `Rock023_2K-PNG`.
+    fn strip_suffix(name: &str) -> Option<(&str, Self)> {
+        [Self::Jpg, Self::Png]
+            .into_iter()
+            .find_map(|variant| Some((name.strip_suffix(variant.to_string().as_str())?.strip_suffix('-')?, variant)))
+    }
 }
 
-#[derive(Clone, Default, Resource)]
-pub struct AmbientCGMaterial<'a> {
-    pub name: &'a str,
-    pub resolution: AmbientCGResolution,
-    pub subfolder: Option<&'a str>,
-    pub uv_scale: Option<Vec2>
+/// A single texture map of an AmbientCG material, identified by the suffix of its file name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TextureChannel {
+    AmbientOcclusion,
+    Color,
+    Displacement,
+    Metalness,
+    NormalGL,
+    /// DirectX convention normal map, converted to OpenGL's when loaded.
+    NormalDX,
+    Roughness,
+    Transmission,
 }
 
-impl<'a> AmbientCGMaterial<'a> {
-    fn negotiate_resolution(self, materials_path: &PathBuf) ->  Result<AmbientCGMaterial<'a>, AmbientCGImportError> {
-        let constructed_material_name = format!("{}_{}-JPG", self.name, self.resolution);
-        let mut resource_path = materials_path.clone();
-        resource_path.push(constructed_material_name);
-        if !&absolute_resource_path(&resource_path).exists() {
-            let resolution = match self.resolution.next_smaller() {
-                Ok(resolution) => resolution,
-                Err(error) => return Err(error)
-            };
-            return AmbientCGMaterial::negotiate_resolution(Self {
-                name: self.name,
-                resolution,
-                subfolder: self.subfolder,
-                uv_scale: self.uv_scale
-            }, materials_path)
+impl TextureChannel {
+    pub const ALL: [TextureChannel; 8] = [
+        Self::AmbientOcclusion,
+        Self::Color,
+        Self::Displacement,
+        Self::Metalness,
+        Self::NormalGL,
+        Self::NormalDX,
+        Self::Roughness,
+        Self::Transmission,
+    ];
+
+    /// Suffix following `{name}_{resolution}-{variant}_` in the channel's file name.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::AmbientOcclusion => "AmbientOcclusion",
+            Self::Color => "Color",
+            Self::Displacement => "Displacement",
+            Self::Metalness => "Metalness",
+            Self::NormalGL => "NormalGL",
+            Self::NormalDX => "NormalDX",
+            Self::Roughness => "Roughness",
+            Self::Transmission => "Transmission",
         }
-        let ambient_cgmaterial = self.clone();
-        Ok(ambient_cgmaterial)
     }
-    pub fn load(
+}
+
+/// Which normal map convention a material is loaded from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NormalConvention {
+    /// Loads `_NormalGL`, falling back to `_NormalDX` when the folder only has the DirectX map.
+    #[default]
+    Auto,
+    /// Only loads `_NormalGL`.
+    OpenGL,
+    /// Only loads `_NormalDX`, e.g. to prefer it when a folder has both.
+    DirectX,
+}
+
+/// Conventional path of every channel of a material, see `AmbientCGMaterial::texture_paths`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaterialTexturePaths {
+    /// Material folder, relative to the assets folder.
+    pub folder: PathBuf,
+    pub ambient_occlusion: PathBuf,
+    pub color: PathBuf,
+    pub displacement: PathBuf,
+    pub metalness: PathBuf,
+    pub normal_gl: PathBuf,
+    pub normal_dx: PathBuf,
+    pub roughness: PathBuf,
+    pub transmission: PathBuf
+}
+
+impl MaterialTexturePaths {
+    pub fn get(&self, channel: TextureChannel) -> &PathBuf {
+        match channel {
+            TextureChannel::AmbientOcclusion => &self.ambient_occlusion,
+            TextureChannel::Color => &self.color,
+            TextureChannel::Displacement => &self.displacement,
+            TextureChannel::Metalness => &self.metalness,
+            TextureChannel::NormalGL => &self.normal_gl,
+            TextureChannel::NormalDX => &self.normal_dx,
+            TextureChannel::Roughness => &self.roughness,
+            TextureChannel::Transmission => &self.transmission,
+        }
+    }
+}
+
+/// Extensions probed for every channel, in order of preference.
+const TEXTURE_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+#[derive(Debug)]
+pub struct AmbientCGImportError(AmbientCGErrorType);
+
+impl AmbientCGImportError {
+    pub fn error_type(&self) -> &AmbientCGErrorType {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+pub enum AmbientCGErrorType {
+    NotFound,
+    InvalidResolution,
+    /// An AmbientCG asset ID that doesn't follow the `{name}_{resolution}-{variant}` convention.
+    InvalidAssetId(String),
+    /// A file could not be read or written, holds the path that failed.
+    Io(PathBuf, std::io::Error),
+    /// A texture file could not be read or decoded, holds its channel and the path that failed. Read failures carry
+    /// an `ImageError::IoError`.
+    Decode(TextureChannel, PathBuf, image::ImageError),
+    /// Bytes supplied for a channel could not be decoded as an image.
+    DecodeBytes(TextureChannel, image::ImageError),
+    /// A mesh lacks a vertex attribute needed to process it, holds the attribute's name.
+    InvalidMesh(&'static str),
+    /// An exported texture could not be written, holds the path that failed.
+    Encode(PathBuf, image::ImageError),
+    /// A channel listed in `AmbientCGConfig::required_channels` has no file.
+    MissingChannel(TextureChannel),
+    /// A channel's map doesn't match the resolution of the rest of the material, holds its channel and path.
+    MixedResolution(TextureChannel, PathBuf),
+    /// No folder of the material was found, holds the file every channel was expected at for the requested
+    /// resolution, to spot packaging mistakes like a misspelled folder.
+    MissingFiles(Vec<(TextureChannel, PathBuf)>),
+    /// A map merged into the metallic-roughness image differs in size from the roughness map, holds its channel,
+    /// its dimensions and those of the roughness map.
+    DimensionMismatch(TextureChannel, (u32, u32), (u32, u32)),
+}
+
+impl AmbientCGErrorType {
+    fn message(&self) -> &'static str {
+        match self {
+            AmbientCGErrorType::NotFound => "Material not found in assets folder",
+            AmbientCGErrorType::InvalidResolution => "Resolution must be one of 1K, 2K, 4K, 8K, 12K or 16K",
+            AmbientCGErrorType::InvalidAssetId(_) => "Asset ID must look like Rock023 or Rock023_2K-JPG",
+            AmbientCGErrorType::Io(..) | AmbientCGErrorType::Decode(_, _, image::ImageError::IoError(_)) => "Texture file could not be read",
+            AmbientCGErrorType::Decode(..) => "Texture file could not be decoded",
+            AmbientCGErrorType::DecodeBytes(..) => "Texture bytes could not be decoded",
+            AmbientCGErrorType::InvalidMesh(_) => "Mesh is missing float positions, normals or UVs",
+            AmbientCGErrorType::Encode(..) => "Texture file could not be written",
+            AmbientCGErrorType::MissingChannel(_) => "Required texture channel is missing",
+            AmbientCGErrorType::MixedResolution(..) => "Texture resolution differs from the rest of the material",
+            AmbientCGErrorType::MissingFiles(_) => "Material files not found in assets folder",
+            AmbientCGErrorType::DimensionMismatch(..) => "Texture dimensions differ from the roughness map it merges with"
+        }
+    }
+}
+
+impl fmt::Display for AmbientCGImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = self.0.message();
+        match &self.0 {
+            AmbientCGErrorType::NotFound | AmbientCGErrorType::InvalidResolution => write!(f, "{}", message),
+            AmbientCGErrorType::InvalidAssetId(id) => write!(f, "{}, got \"{}\"", message, id),
+            AmbientCGErrorType::Io(path, _)
+            | AmbientCGErrorType::Encode(path, _) => write!(f, "{}: {}", message, path.display()),
+            AmbientCGErrorType::Decode(channel, path, _)
+            | AmbientCGErrorType::MixedResolution(channel, path) => write!(f, "{}: {} at {}", message, channel.suffix(), path.display()),
+            AmbientCGErrorType::DecodeBytes(channel, _) => write!(f, "{} for channel {}", message, channel.suffix()),
+            AmbientCGErrorType::InvalidMesh(attribute) => write!(f, "{}, lacking {}", message, attribute),
+            AmbientCGErrorType::MissingChannel(channel) => write!(f, "{}: {}", message, channel.suffix()),
+            AmbientCGErrorType::DimensionMismatch(channel, (width, height), (expected_width, expected_height)) => write!(
+                f,
+                "{}: {} is {}x{}, roughness is {}x{}",
+                message,
+                channel.suffix(),
+                width,
+                height,
+                expected_width,
+                expected_height
+            ),
+            AmbientCGErrorType::MissingFiles(files) => {
+                write!(f, "{}, expected", message)?;
+                for (channel, path) in files {
+                    write!(f, "\n  {} at {}", channel.suffix(), path.display())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for AmbientCGImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.0 {
+            AmbientCGErrorType::Io(_, error) => Some(error),
+            AmbientCGErrorType::Decode(_, _, error)
+            | AmbientCGErrorType::DecodeBytes(_, error)
+            | AmbientCGErrorType::Encode(_, error) => Some(error),
+            AmbientCGErrorType::NotFound
+            | AmbientCGErrorType::InvalidResolution
+            | AmbientCGErrorType::InvalidAssetId(_)
+            | AmbientCGErrorType::InvalidMesh(_)
+            | AmbientCGErrorType::MissingChannel(_)
+            | AmbientCGErrorType::MixedResolution(..)
+            | AmbientCGErrorType::MissingFiles(_)
+            | AmbientCGErrorType::DimensionMismatch(..) => None
+        }
+    }
+}
+
+/// An AmbientCG material folder to load into a `StandardMaterial`. A folder needs at least a `_Color` or `_NormalGL`
+/// map to produce a useful material, folders holding only e.g. `_Displacement` or `_AmbientOcclusion` log a warning.
+#[derive(Clone, Debug, Resource)]
+pub struct AmbientCGMaterial<'a> {
+    pub name: &'a str,
+    pub resolution: AmbientCGResolution,
+    /// Download variant, selecting between `{name}_{resolution}-JPG` and `{name}_{resolution}-PNG` folders.
+    pub variant: AmbientCGVariant,
+    /// Folder under `materials_path` holding the material's resolution folders. A subfolder ending in one of those
+    /// folders, e.g. `rocks/Rock023_2K-JPG`, is read as its parent, so the material folder isn't joined twice.
+    pub subfolder: Option<&'a str>,
+    /// Subfolders overriding `subfolder` for single channels, e.g. `&[(TextureChannel::NormalGL, "bricks/shared")]`
+    /// to share one normal map between color variants in sibling folders. The override folder follows the same
+    /// `{name}_{resolution}-{variant}` layout and uses the resolution negotiated for the material's own folder.
+    pub channel_subfolders: &'a [(TextureChannel, &'a str)],
+    /// Files loaded for single channels instead of the conventional name, e.g.
+    /// `&[(TextureChannel::Roughness, "materials/odd/rough_final.png")]` for a pack named off-convention. Paths are
+    /// relative to the assets folder unless absolute, and are probed like conventional ones, so a `.jpg`, `.jpeg` or
+    /// `.png` extension is expected.
+    pub channel_paths: &'a [(TextureChannel, &'a str)],
+    /// Tiling of the material's UVs. Negative components mirror the material, but mesh tangents don't know about the
+    /// mirroring so lighting from the normal map is inverted along that axis; prefer `mirror_u`/`mirror_v`, which
+    /// also correct the normal map.
+    pub uv_scale: Option<Vec2>,
+    /// Scales the XY components of the normal map at load time, values below 1.0 flatten it and values above 1.0
+    /// exaggerate it. `None` loads the normal map unchanged.
+    pub normal_strength: Option<f32>,
+    /// Treats the `_Roughness` map as glossiness and inverts it (roughness = 1 - gloss) when building the
+    /// metallic-roughness texture.
+    pub roughness_is_gloss: bool,
+    /// Blends the `_AmbientOcclusion` map toward white at load time, 0.0 disables occlusion and 1.0 keeps it
+    /// unchanged. `None` loads the map unchanged.
+    pub occlusion_strength: Option<f32>,
+    /// Skips the separate `_AmbientOcclusion` map for packs that already bake occlusion into the color map.
+    pub occlusion_in_color: bool,
+    /// Renders both faces by enabling `double_sided` and disabling back face culling, e.g. for foliage and cloth cards.
+    pub double_sided: bool,
+    /// Inverts the green (Y) channel of the normal map at load time, for maps authored with the opposite convention.
+    pub flip_normal_y: bool,
+    /// Mirrors the material horizontally by negating the U scale, and flips the normal map's X (red) channel to match.
+    pub mirror_u: bool,
+    /// Mirrors the material vertically by negating the V scale, and flips the normal map's Y (green) channel to match.
+    pub mirror_v: bool,
+    /// Overrides `StandardMaterial::specular_transmission`. With a `_Transmission` map present it defaults to 1.0 so
+    /// the map drives transmission, otherwise the material stays opaque.
+    pub specular_transmission: Option<f32>,
+    /// Loads the `_Color` map as linear instead of sRGB, for packs that store albedo in linear space. Every other map
+    /// holds data and is always loaded as linear.
+    pub linear_color: bool,
+    /// Repeats the textures across UVs outside 0..1. Turn it off for decals so they clamp to their edges instead,
+    /// overriding `AmbientCGConfig::address_mode` for this material only.
+    pub tiling: bool,
+    /// Which of `_NormalGL` and `_NormalDX` to load. DirectX maps have their green channel flipped into OpenGL's
+    /// convention, which is what Bevy expects.
+    pub normal_convention: NormalConvention,
+    /// Tint multiplied over the `_Color` map, e.g. to vary one stone material between mossy and dry instances.
+    /// `None` keeps the texture's colors as they are.
+    pub base_color: Option<Color>,
+    /// Overrides `StandardMaterial::perceptual_roughness`, which multiplies the roughness map, e.g. `Some(1.2)` for a
+    /// 20% rougher surface. Defaults to 1.0 with a map so the texture is used as is.
+    pub perceptual_roughness: Option<f32>,
+    /// Overrides `StandardMaterial::metallic`, which multiplies the metalness map. Defaults to 1.0 with a map and 0.0
+    /// without one.
+    pub metallic: Option<f32>,
+    /// Overrides `StandardMaterial::reflectance`, the specular reflectance of dielectrics, where Bevy's default 0.5 is
+    /// 4% reflectance at normal incidence, an IOR of 1.5. `reflectance_from_ior` converts a measured IOR, e.g. 1.33
+    /// for water.
+    pub reflectance: Option<f32>,
+    /// Reads metalness from the alpha channel of this channel's map when the folder has no `_Metalness` file, for
+    /// atlas packs storing it e.g. in the alpha of `_Color`. A map without alpha leaves the material without
+    /// metalness.
+    pub metalness_from_alpha: Option<TextureChannel>,
+    /// Approximates roughness from the luminance of the `_Color` map when the folder has no `_Roughness` file. This is
+    /// a heuristic that only holds for materials whose brightness tracks their roughness, e.g. dusty or worn surfaces,
+    /// and reads bright albedo as rough. Combine it with `roughness_is_gloss` for packs where bright areas are polished.
+    pub roughness_from_color: bool,
+    /// Overrides `StandardMaterial::cull_mode`, `Some(None)` disables culling without making the material double
+    /// sided. Defaults to back face culling, or none for `double_sided` materials.
+    pub cull_mode: Option<Option<Face>>,
+    /// Overrides `StandardMaterial::depth_bias`, e.g. to keep decals from z-fighting with the surface under them.
+    pub depth_bias: Option<f32>,
+    /// Overrides `StandardMaterial::fog_enabled`, which defaults to `true`.
+    pub fog_enabled: Option<bool>
+}
+
+impl Default for AmbientCGMaterial<'_> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// Float fields are compared by their bits so that equality agrees with Hash, which makes descriptors usable as
+// HashMap keys. This means 0.0 and -0.0 differ while NaN equals itself.
+impl PartialEq for AmbientCGMaterial<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for AmbientCGMaterial<'_> {}
+
+impl Hash for AmbientCGMaterial<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
+
+impl<'a> AmbientCGMaterial<'a> {
+    /// Default values usable in `const` items through struct update syntax, `..AmbientCGMaterial::DEFAULT`.
+    pub const DEFAULT: Self = Self {
+        name: "",
+        resolution: AmbientCGResolution::OneK,
+        variant: AmbientCGVariant::Jpg,
+        subfolder: None,
+        channel_subfolders: &[],
+        channel_paths: &[],
+        uv_scale: None,
+        normal_strength: None,
+        roughness_is_gloss: false,
+        occlusion_strength: None,
+        occlusion_in_color: false,
+        double_sided: false,
+        flip_normal_y: false,
+        mirror_u: false,
+        mirror_v: false,
+        specular_transmission: None,
+        linear_color: false,
+        tiling: true,
+        normal_convention: NormalConvention::Auto,
+        base_color: None,
+        perceptual_roughness: None,
+        metallic: None,
+        reflectance: None,
+        metalness_from_alpha: None,
+        roughness_from_color: false,
+        cull_mode: None,
+        depth_bias: None,
+        fog_enabled: None
+    };
+
+    /// Converts an index of refraction to Bevy's `reflectance`. Bevy maps reflectance `r` to a reflectance at normal
+    /// incidence of `0.16 * r²`, and Fresnel gives `((ior - 1) / (ior + 1))²`, so `r = (ior - 1) / (ior + 1) / 0.4`.
+    pub fn reflectance_from_ior(ior: f32) -> f32 {
+        (ior - 1.0) / (ior + 1.0) / 0.4
+    }
+    /// A material with default settings at `config.default_resolution`.
+    pub fn with_name(name: &'a str, config: &AmbientCGConfig) -> Self {
+        Self {
+            name,
+            resolution: config.default_resolution,
+            ..Self::DEFAULT
+        }
+    }
+    /// Builds a material from an AmbientCG asset ID, either the bare `"Rock023"` or the download name
+    /// `"Rock023_2K-JPG"` / `"Rock023_2K-PNG"`. A bare ID uses `AmbientCGConfig::default_resolution` and the default
+    /// variant.
+    pub fn from_asset_id(asset_id: &'a str, config: &AmbientCGConfig) -> Result<Self, AmbientCGImportError> {
+        let invalid = || AmbientCGImportError(AmbientCGErrorType::InvalidAssetId(asset_id.to_string()));
+        let (id, variant) = AmbientCGVariant::strip_suffix(asset_id).unwrap_or((asset_id, AmbientCGVariant::default()));
+        let (name, resolution) = match id.split_once('_') {
+            Some((name, resolution)) => (name, resolution.parse().map_err(|_| invalid())?),
+            None => (id, config.default_resolution)
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            name,
+            resolution,
+            variant,
+            ..Self::DEFAULT
+        })
+    }
+    /// Every field in a hashable form, destructured so that a new field can't be forgotten in `PartialEq` and `Hash`.
+    fn identity(&self) -> impl Hash + Eq + '_ {
+        let Self {
+            name,
+            resolution,
+            variant,
+            subfolder,
+            channel_subfolders,
+            channel_paths,
+            uv_scale,
+            normal_strength,
+            roughness_is_gloss,
+            occlusion_strength,
+            occlusion_in_color,
+            double_sided,
+            flip_normal_y,
+            mirror_u,
+            mirror_v,
+            specular_transmission,
+            linear_color,
+            tiling,
+            normal_convention,
+            base_color,
+            perceptual_roughness,
+            metallic,
+            reflectance,
+            metalness_from_alpha,
+            roughness_from_color,
+            cull_mode,
+            depth_bias,
+            fog_enabled
+        } = self;
+        let bits = |value: &Option<f32>| value.map(f32::to_bits);
+        (
+            (*name, *resolution, *variant, *subfolder, *channel_subfolders, *channel_paths, uv_scale.map(|scale| scale.to_array().map(f32::to_bits))),
+            (bits(normal_strength), bits(occlusion_strength), bits(specular_transmission), bits(perceptual_roughness), bits(metallic), bits(reflectance), bits(depth_bias)),
+            (*roughness_is_gloss, *occlusion_in_color, *double_sided, *flip_normal_y, *mirror_u, *mirror_v, *linear_color, *tiling, *roughness_from_color),
+            (*normal_convention, base_color.map(|color| color.to_linear().to_f32_array().map(f32::to_bits)), *metalness_from_alpha, *cull_mode, *fog_enabled)
+        )
+    }
+    /// Folder name and file prefix of this material, `{name}_{resolution}-{variant}`.
+    fn folder_name(&self) -> String {
+        format!("{}_{}-{}", self.name, self.resolution, self.variant)
+    }
+    /// Lists the resolutions of this material present under the configured materials path, largest first.
+    pub fn available_resolutions(&self, config: &AmbientCGConfig) -> Vec<AmbientCGResolution> {
+        self.scan_resolutions(&self.materials_path(config), config)
+    }
+    /// Highest resolution every one of `members` has on disk and none of them requested less than, so a themed set
+    /// like wall, floor and trim loads at matching sharpness even if one member has a larger variant the others lack.
+    /// Fails with `NotFound` if the members share no resolution.
+    pub fn shared_resolution(members: &[AmbientCGMaterial<'_>], config: &AmbientCGConfig) -> Result<AmbientCGResolution, AmbientCGImportError> {
+        let Some((first, rest)) = members.split_first() else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::NotFound));
+        };
+        let rest: Vec<Vec<AmbientCGResolution>> = rest.iter().map(|member| member.available_resolutions(config)).collect();
+        first.available_resolutions(config)
+            .into_iter()
+            .filter(|resolution| members.iter().all(|member| *resolution <= member.resolution))
+            .find(|resolution| rest.iter().all(|available| available.contains(resolution)))
+            .ok_or(AmbientCGImportError(AmbientCGErrorType::NotFound))
+    }
+    /// Loads every one of `members` at their `shared_resolution`, panicking if they share none or one fails to load.
+    /// Use `try_load_matched` to handle those errors instead.
+    pub fn load_matched(
+        members: &[AmbientCGMaterial<'_>],
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Vec<Handle<StandardMaterial>> {
+        expect_loaded(Self::try_load_matched(members, asset_server, materials, config, state))
+    }
+    /// Loads every one of `members` at their `shared_resolution`, returning the handles in the same order.
+    pub fn try_load_matched(
+        members: &[AmbientCGMaterial<'_>],
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Vec<Handle<StandardMaterial>>, AmbientCGImportError> {
+        let resolution = Self::shared_resolution(members, config)?;
+        members
+            .iter()
+            .map(|member| AmbientCGMaterial { resolution, ..member.clone() }.try_load(asset_server, materials, config, state))
+            .collect()
+    }
+    /// Resolutions of this material's folders in `materials_path`, largest first. The folder is listed once instead
+    /// of probing every resolution with its own `stat`.
+    fn scan_resolutions(&self, materials_path: &Path, config: &AmbientCGConfig) -> Vec<AmbientCGResolution> {
+        // HDRIs are single files, their name is the folder name of the other variants plus an extension
+        let parse = |folder_name: &str| match self.variant {
+            AmbientCGVariant::Hdr => self.parse_folder_name(Path::new(folder_name).file_stem()?.to_str()?),
+            _ => self.parse_folder_name(folder_name)
+        };
+
+        let entries = config.file_system.read_dir(&config.file_path(materials_path));
+        report_probe(materials_path, entries.is_ok(), config);
+        let mut resolutions: Vec<AmbientCGResolution> = entries
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let resolution = parse(entry.to_str()?)?;
+                report_probe(&materials_path.join(&entry), true, config);
+                Some(resolution)
+            })
+            .collect();
+        #[cfg(feature = "embedded")]
+        resolutions.extend(config.embedded_textures.keys().filter_map(|embedded| {
+            let folder = embedded.strip_prefix(materials_path).ok()?.components().next()?;
+            parse(folder.as_os_str().to_str()?)
+        }));
+        if resolutions.is_empty() {
+            report_probe(&materials_path.join(self.folder_name()), false, config);
+        }
+        resolutions.sort_unstable_by(|a, b| b.cmp(a));
+        resolutions.dedup();
+        resolutions
+    }
+    pub fn load(
         &self,
         asset_server: &Res<'_, AssetServer>,
         materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
     ) -> Handle<StandardMaterial> {
-        if let Some(uv_scale) = self.uv_scale {
-            return self.load_with_uv_scale(asset_server, materials, uv_scale);
-        }
-        self.load_without_uv_scale(asset_server, materials)
+        expect_loaded(self.try_load(asset_server, materials, config, state))
     }
     pub fn load_without_uv_scale(
         &self,
         asset_server: &Res<'_, AssetServer>,
-        materials: &mut ResMut<'_, Assets<StandardMaterial>>
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
     ) -> Handle<StandardMaterial> {
-        self.load_with_uv_scale(asset_server, materials, Vec2::ZERO)
+        self.load_with_uv_scale(asset_server, materials, Vec2::ZERO, config, state)
     }
+    /// Loads the material, panicking if it can't be found or one of its textures fails to decode. Use
+    /// `try_load_with_uv_scale` to handle those errors instead.
     pub fn load_with_uv_scale(
         &self,
         asset_server: &Res<'_, AssetServer>,
         materials: &mut ResMut<'_, Assets<StandardMaterial>>,
-        uv_scale: Vec2
+        uv_scale: Vec2,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Handle<StandardMaterial> {
+        expect_loaded(self.try_load_with_uv_scale(asset_server, materials, uv_scale, config, state))
+    }
+    /// Tiles the material at a fixed texel density instead of a fixed repeat count: a surface `world_size` units
+    /// across repeats the texture once every `texture_world_size` units, e.g. `1.0` for a one meter tile on terrain.
+    pub fn load_with_world_scale(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        world_size: Vec2,
+        texture_world_size: f32,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Handle<StandardMaterial> {
+        self.load_with_uv_scale(asset_server, materials, world_uv_scale(world_size, texture_world_size), config, state)
+    }
+    pub fn try_load_with_world_scale(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        world_size: Vec2,
+        texture_world_size: f32,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        self.try_load_with_uv_scale(asset_server, materials, world_uv_scale(world_size, texture_world_size), config, state)
+    }
+    /// Tiles the material `repeats` times across the largest dimension of `mesh`'s bounding box, scaling the other
+    /// direction to keep texels square, e.g. for walls and floors sized at runtime. `u` follows the largest dimension
+    /// and `v` the second largest, as on planes and on the long faces of cuboids.
+    #[allow(clippy::too_many_arguments)]
+    pub fn load_fitted_to_mesh(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        meshes: &Assets<Mesh>,
+        mesh: &Handle<Mesh>,
+        repeats: f32,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
     ) -> Handle<StandardMaterial> {
-        let mut material_path =PathBuf::from_str(&MATERIALS_PATH.lock().unwrap()).unwrap();
+        expect_loaded(self.try_load_fitted_to_mesh(asset_server, materials, meshes, mesh, repeats, config, state))
+    }
+    /// Fails with `NotFound` if `mesh` isn't in `meshes` and `InvalidMesh` if it has no extent to fit to.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_load_fitted_to_mesh(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        meshes: &Assets<Mesh>,
+        mesh: &Handle<Mesh>,
+        repeats: f32,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        let Some(mesh) = meshes.get(mesh) else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::NotFound));
+        };
+        let Some(uv_scale) = mesh_uv_scale(mesh, repeats) else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::InvalidMesh("positions")));
+        };
+        self.try_load_with_uv_scale(asset_server, materials, uv_scale, config, state)
+    }
+    /// `load` wrapped in the `MeshMaterial3d` component, ready to go into a spawn tuple.
+    pub fn load_component(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> MeshMaterial3d<StandardMaterial> {
+        MeshMaterial3d(self.load(asset_server, materials, config, state))
+    }
+    /// `load_with_uv_scale` wrapped in the `MeshMaterial3d` component, ready to go into a spawn tuple.
+    pub fn load_component_with_uv_scale(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        uv_scale: Vec2,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> MeshMaterial3d<StandardMaterial> {
+        MeshMaterial3d(self.load_with_uv_scale(asset_server, materials, uv_scale, config, state))
+    }
+    pub fn try_load(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        self.try_load_with_uv_scale(asset_server, materials, self.effective_uv_scale(config), config, state)
+    }
+    pub fn try_load_with_uv_scale(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        uv_scale: Vec2,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        let key = config.reuse_materials.then(|| self.loaded_material_key(uv_scale, config, state));
+        if let Some(key) = &key {
+            let id = state.0.loaded_materials.lock().unwrap().get(key).copied();
+            // the first handle may have been dropped since, which frees the material
+            if let Some(handle) = id.and_then(|id| materials.get_strong_handle(id)) {
+                return Ok(handle);
+            }
+        }
 
-        if let Some(subfolder) = &self.subfolder {
-            material_path.push(subfolder);
+        let Some(textures) = self.load_counted(asset_server, config, state)? else {
+            return Ok(Handle::default());
+        };
+        let resolution = textures.resolution;
+        let handle = materials.add(self.configured_material(textures, uv_scale, config));
+        self.register(&handle, resolution, state);
+        if let Some(key) = key {
+            state.0.loaded_materials.lock().unwrap().insert(key, handle.id());
+        }
+        Ok(handle)
+    }
+    /// Loads the material from `materials_path` instead of the configured one, e.g. to keep a mod library under
+    /// `mods/materials` next to the stock one. Panics like `load`, use `try_load_from` to handle errors instead.
+    pub fn load_from(
+        &self,
+        materials_path: &Path,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Handle<StandardMaterial> {
+        expect_loaded(self.try_load_from(materials_path, asset_server, materials, config, state))
+    }
+    pub fn try_load_from(
+        &self,
+        materials_path: &Path,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        let config = AmbientCGConfig {
+            materials_path: materials_path.to_path_buf(),
+            ..config.clone()
+        };
+        self.try_load_with_uv_scale(asset_server, materials, self.effective_uv_scale(&config), &config, state)
+    }
+    /// Loads the material from `dir`, a folder outside the assets folder holding material folders, e.g. a mod
+    /// directory chosen at runtime. Relative paths are resolved against the working directory. Panics like `load`,
+    /// use `try_load_from_absolute` to handle errors instead.
+    ///
+    /// Textures are read through the default asset source, which resolves absolute paths on desktop platforms only.
+    /// Bevy refuses paths outside the assets folder by default, so `AssetPlugin::unapproved_path_mode` must be
+    /// `UnapprovedPathMode::Deny` or `Allow`.
+    pub fn load_from_absolute(
+        &self,
+        dir: &Path,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Handle<StandardMaterial> {
+        expect_loaded(self.try_load_from_absolute(dir, asset_server, materials, config, state))
+    }
+    pub fn try_load_from_absolute(
+        &self,
+        dir: &Path,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        let dir = std::path::absolute(dir)
+            .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Io(dir.to_path_buf(), error)))?;
+        self.try_load_from(&dir, asset_server, materials, config, state)
+    }
+    /// `uv_scale`, falling back to `AmbientCGConfig::default_uv_scale` and then to `Vec2::ZERO`, which leaves the UVs
+    /// unscaled.
+    fn effective_uv_scale(&self, config: &AmbientCGConfig) -> Vec2 {
+        self.uv_scale.or(config.default_uv_scale).unwrap_or(Vec2::ZERO)
+    }
+    fn loaded_material_key(&self, uv_scale: Vec2, config: &AmbientCGConfig, state: &AmbientCGState) -> LoadedMaterialKey {
+        let transform = self.configured_material(MaterialTextures::default(), uv_scale, config).uv_transform;
+        let uv_transform = [
+            transform.matrix2.x_axis.x,
+            transform.matrix2.x_axis.y,
+            transform.matrix2.y_axis.x,
+            transform.matrix2.y_axis.y,
+            transform.translation.x,
+            transform.translation.y
+        ];
+        LoadedMaterialKey {
+            name: self.name.to_string(),
+            subfolder: self.subfolder.map(str::to_string),
+            materials_path: config.materials_path.clone(),
+            descriptor: self.detached(state),
+            uv_transform: uv_transform.map(f32::to_bits)
+        }
+    }
+    /// Loads the textures into a custom material type, e.g. a terrain `ExtendedMaterial`, panicking like `load` on
+    /// errors. Use `try_load_as` to handle those errors instead.
+    pub fn load_as<M: FromAmbientCGTextures + Asset>(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<M>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Handle<M> {
+        expect_loaded(self.try_load_as(asset_server, materials, config, state))
+    }
+    /// Loads the textures into a custom material type. The material isn't tracked by `AmbientCGRegistry`, which only
+    /// holds `StandardMaterial`s.
+    pub fn try_load_as<M: FromAmbientCGTextures + Asset>(
+        &self,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<M>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<M>, AmbientCGImportError> {
+        let Some(textures) = self.load_counted(asset_server, config, state)? else {
+            return Ok(Handle::default());
+        };
+        Ok(materials.add(M::from_ambient_cg_textures(textures, self)))
+    }
+    /// Probes, decodes and merges the textures on the `AsyncComputeTaskPool`, resolving once the material is added
+    /// and registered. Panics like `load` on errors, use `try_load_async` to handle them instead.
+    ///
+    /// The material lands in `Assets<StandardMaterial>` with the asset events of the next frame, like any handle
+    /// returned by `AssetServer::add`.
+    pub fn load_async(&self, asset_server: &AssetServer, config: &AmbientCGConfig, state: &AmbientCGState) -> impl Future<Output = Handle<StandardMaterial>> + 'static {
+        let load = self.try_load_async(asset_server, config, state);
+        async move { expect_loaded(load.await) }
+    }
+    pub fn try_load_async(
+        &self,
+        asset_server: &AssetServer,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> impl Future<Output = Result<Handle<StandardMaterial>, AmbientCGImportError>> + 'static {
+        let asset_server = asset_server.clone();
+        let config = config.clone();
+        let name = self.name.to_string();
+        let subfolder = self.subfolder.map(str::to_string);
+        let descriptor = self.detached(state);
+        let state = state.clone();
+        AsyncComputeTaskPool::get().spawn(async move {
+            let material = AmbientCGMaterial { name: &name, subfolder: subfolder.as_deref(), ..descriptor };
+            let Some(textures) = material.load_counted(&asset_server, &config, &state)? else {
+                return Ok(Handle::default());
+            };
+            let resolution = textures.resolution;
+            let uv_scale = material.effective_uv_scale(&config);
+            let handle = asset_server.add(material.configured_material(textures, uv_scale, &config));
+            material.register(&handle, resolution, &state);
+            Ok(handle)
+        })
+    }
+    /// Spawns a unit diameter UV sphere at the origin with this material, the standard preview for material browsers
+    /// and editor tooling. Panics like `load` if the material cannot be loaded.
+    pub fn spawn_preview(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<'_, Assets<Mesh>>,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Entity {
+        let sphere = meshes.add(Sphere::new(0.5).mesh().uv(64, 32));
+        commands.spawn_ambient_cg(sphere, self, asset_server, materials, Transform::default(), config, state).id()
+    }
+    /// Loads this material into the `Assets` entry at `handle` instead of adding a new one, so a handle allocated up
+    /// front (e.g. with `Handle::weak_from_u128` or `materials.reserve_handle()`) stays valid for everything that
+    /// already references it. An existing material at `handle` is replaced.
+    pub fn load_into(
+        &self,
+        handle: &Handle<StandardMaterial>,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<(), AmbientCGImportError> {
+        let Some(textures) = self.load_counted(asset_server, config, state)? else {
+            return Ok(());
+        };
+        let resolution = textures.resolution;
+        let uv_scale = self.effective_uv_scale(config);
+        materials.insert(handle, self.configured_material(textures, uv_scale, config));
+        self.register(handle, resolution, state);
+        Ok(())
+    }
+    /// Loads this material's maps at `resolution` and swaps them into the existing material behind `handle`, so every
+    /// entity using the handle picks up the new textures. Scalars and the UV transform of the material are left as they are.
+    pub fn swap_resolution(
+        &self,
+        handle: &Handle<StandardMaterial>,
+        resolution: AmbientCGResolution,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<(), AmbientCGImportError> {
+        if config.headless {
+            return Ok(());
         }
 
-        let mut ambient_cg_material = self.clone();
-        if *RESOLUTION_NEGOTIATION.lock().unwrap() {
-            ambient_cg_material = match self.clone().negotiate_resolution(&material_path) {
-                Ok(ambient_cg_material) => {
-                    let ambient_cgmaterial = ambient_cg_material.to_owned();
-                    ambient_cgmaterial
-                },
-                Err(err) => panic!("{}", err)
-            }
+        let textures = Self { resolution, ..self.clone() }.load_textures(asset_server, config, state)?;
+        let Some(material) = materials.get_mut(handle) else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::NotFound));
+        };
+        self.register(handle, textures.resolution, state);
+        material.base_color_texture = textures.base_color;
+        material.metallic_roughness_texture = textures.metallic_roughness;
+        material.normal_map_texture = textures.normal_map;
+        material.occlusion_texture = textures.occlusion;
+        material.thickness_texture = textures.thickness;
+        material.specular_transmission_texture = textures.specular_transmission;
+        Ok(())
+    }
+    /// Builds a material from texture bytes already in memory, e.g. received over the network, without touching the
+    /// assets folder. Each blob is decoded by content, so any format supported by the `image` crate works.
+    pub fn load_from_bytes(
+        &self,
+        channels: HashMap<TextureChannel, Vec<u8>>,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        if config.headless {
+            return Ok(Handle::default());
         }
 
-        let constructed_material_name = format!("{}_{}-JPG", ambient_cg_material.name, ambient_cg_material.resolution);
-        material_path.push(constructed_material_name.clone());
-        
-        let occlusion_path = material_path.join(constructed_material_name.clone() + "_AmbientOcclusion").with_extension("jpg");
-        let base_color_path = material_path.join(constructed_material_name.clone() + "_Color").with_extension("jpg");
-        let thickness_path = material_path.join(constructed_material_name.clone() + "_Displacement").with_extension("jpg");
-        let metallic_texture_path = material_path.join(constructed_material_name.clone() + "_Metalness").with_extension("jpg");
-        let normal_map_path = material_path.join(constructed_material_name.clone() + "_NormalGL").with_extension("jpg");
-        let roughness_texture_path = material_path.join(constructed_material_name.clone() + "_Roughness").with_extension("jpg");
-
-        let repeat_texture = 
-        |s: &mut _| {
-            *s = ImageLoaderSettings {
-                sampler: ImageSampler::Descriptor(ImageSamplerDescriptor {
-                    // rewriting mode to repeat image,
-                    address_mode_u: ImageAddressMode::Repeat,
-                    address_mode_v: ImageAddressMode::Repeat,
-                    ..default()
-                }),
-                ..default()
-            }
+        if let Some(channel) = config.required_channels.iter().find(|channel| !channels.contains_key(*channel)) {
+            return Err(AmbientCGImportError(AmbientCGErrorType::MissingChannel(*channel)));
+        }
+
+        let config = self.sampler_config(config);
+        let decode = |channel: TextureChannel| {
+            let decoded = channels.get(&channel)
+                .map(|bytes| image::load_from_memory(bytes)
+                    .map(|image| fit_texture_size(image, &config))
+                    .map_err(|error| AmbientCGImportError(AmbientCGErrorType::DecodeBytes(channel, error))))
+                .transpose();
+            lenient(decoded, &config).map(Option::flatten)
         };
+        let material_name = self.folder_name();
+        let add = |image: Image, channel: &str| asset_server.add(labeled_image(image, &material_name, channel, &config, state));
 
-        let occlusion_texture_exists = Path::exists(&absolute_resource_path(&occlusion_path));
-        let base_color_texture_exists = Path::exists(&absolute_resource_path(&base_color_path));
-        let thickness_texture_exists = Path::exists(&absolute_resource_path(&thickness_path));
-        let metallic_texture_exists = Path::exists(&absolute_resource_path(&metallic_texture_path));
-        let normal_map_texture_exists = Path::exists(&absolute_resource_path(&normal_map_path));
-        let roughness_texture_exists = Path::exists(&absolute_resource_path(&roughness_texture_path));
-        
-        let occlusion_texture: Option<Handle<Image>> = if occlusion_texture_exists {Some(asset_server.load_with_settings(occlusion_path, repeat_texture))} else { None };
-        let base_color_texture: Option<Handle<Image>> = if base_color_texture_exists {Some(asset_server.load_with_settings(base_color_path, repeat_texture))} else { None };
-        let thickness_texture: Option<Handle<Image>> = if thickness_texture_exists {Some(asset_server.load_with_settings(thickness_path, repeat_texture))} else { None };
-        let normal_map_texture: Option<Handle<Image>> = if normal_map_texture_exists {Some(asset_server.load_with_settings(normal_map_path, repeat_texture))} else { None };
-
-        let mut metallic_roughness_texture = None;
-        if metallic_texture_exists && roughness_texture_exists {
-            metallic_roughness_texture = Some(asset_server.add(
-                create_roughness_metallic_image(
-                    absolute_resource_path(&metallic_texture_path),
-                    absolute_resource_path(&roughness_texture_path)
-                )));
-        } else if metallic_texture_exists {
-            metallic_roughness_texture = Some(asset_server.load_with_settings(metallic_texture_path, repeat_texture));
-        } else if roughness_texture_exists {
-            metallic_roughness_texture = Some(asset_server.load_with_settings(roughness_texture_path, repeat_texture));
-        }
-
-        let material = StandardMaterial {
-            base_color_texture,
-            metallic_roughness_texture,
-            metallic: 1.0,
-            normal_map_texture,
-            occlusion_texture,
-            perceptual_roughness: 1.0,
-            thickness_texture,
-            uv_transform: (|| {
-                if uv_scale == Vec2::ZERO {
-                    return Affine2::default();
-                }
-                Affine2::from_scale(uv_scale)
-            })(),
-            ..default()
+        let occlusion = decode(TextureChannel::AmbientOcclusion)?.filter(|_| !self.occlusion_in_color);
+        let metallic = match (decode(TextureChannel::Metalness)?, self.metalness_from_alpha) {
+            (None, Some(channel)) => decode(channel)?.and_then(|image| alpha_channel(&image)).map(DynamicImage::ImageLuma8),
+            (metallic, _) => metallic
         };
-        materials.add(material)
-    }
-}
+        let roughness = match decode(TextureChannel::Roughness)? {
+            None if self.roughness_from_color => decode(TextureChannel::Color)?.map(|image| to_grayscale(image, TextureChannel::Color, &config)),
+            roughness => roughness
+        };
+        let normal = match self.normal_channels().iter().find(|channel| channels.contains_key(*channel)) {
+            Some(channel) => decode(*channel)?.map(|image| (image, *channel)),
+            None => None
+        };
+        let has_metalness = metallic.is_some();
+        let has_roughness = roughness.is_some();
 
-fn absolute_resource_path(p: &PathBuf) -> PathBuf {
-    let mut path = FileAssetReader::get_base_path();
-    let p = p.clone().into_os_string();
-    let s = OsStr::new("assets");
-    path.push(s);
-    path.push(p);
-    path
-}
+        let packs_occlusion = config.pack_occlusion && has_metalness && has_roughness;
+        let (packed_occlusion, occlusion) = if packs_occlusion {
+            (occlusion, None)
+        } else {
+            (None, occlusion)
+        };
+        let occlusion_packed = packed_occlusion.is_some();
+
+        let metallic_roughness = match (metallic, roughness) {
+            (Some(metallic), Some(roughness)) => Some(add_merged_image(
+                asset_server,
+                MergeInputs::new(
+                    to_grayscale(roughness, TextureChannel::Roughness, &config),
+                    to_grayscale(metallic, TextureChannel::Metalness, &config),
+                    packed_occlusion.map(|occlusion| to_grayscale(occlusion, TextureChannel::AmbientOcclusion, &config)),
+                    self.occlusion_strength,
+                    self.roughness_is_gloss,
+                    config.channel_layout,
+                    &config
+                )?,
+                &material_name,
+                &config, state
+            )),
+            (Some(image), None) | (None, Some(image)) => Some(add(generated_image(image, false), "MetallicRoughness")),
+            (None, None) => None
+        };
+        let occlusion = if occlusion_packed {
+            metallic_roughness.clone()
+        } else {
+            occlusion.map(|image| add(
+                attenuate_occlusion_image(to_grayscale(image, TextureChannel::AmbientOcclusion, &config), self.occlusion_strength),
+                TextureChannel::AmbientOcclusion.suffix()
+            ))
+        };
 
-fn create_roughness_metallic_image(roughness_path: PathBuf, metallic_path: PathBuf) -> Image {
-    let roughness = load_grayscale_image(&roughness_path);
-    let metallic = load_grayscale_image(&metallic_path);
+        let mut textures = MaterialTextures {
+            base_color: decode(TextureChannel::Color)?
+                .map(|image| add(generated_image(image, self.is_srgb(TextureChannel::Color)), TextureChannel::Color.suffix())),
+            metallic_roughness,
+            normal_map: normal
+                .map(|(image, channel)| add(adjust_normal(image, self.normal_adjustments(channel, &config)), TextureChannel::NormalGL.suffix())),
+            occlusion,
+            thickness: decode(TextureChannel::Displacement)?
+                .map(|image| add(generated_image(image, false), TextureChannel::Displacement.suffix())),
+            specular_transmission: decode(TextureChannel::Transmission)?
+                .map(|image| add(generated_image(image, false), TextureChannel::Transmission.suffix())),
+            has_metalness,
+            has_roughness,
+            resolution: self.resolution
+        };
+        if !textures.has_surface() {
+            warn!("AmbientCG material {} was given neither a color nor a normal map", material_name);
+        }
+        if config.flat_normal_fallback && textures.normal_map.is_none() {
+            textures.normal_map = Some(add(flat_normal_image(), "FlatNormal"));
+        }
+        let uv_scale = self.effective_uv_scale(&config);
+        let handle = materials.add(self.configured_material(textures, uv_scale, &config));
+        self.register(&handle, self.resolution, state);
+        Ok(handle)
+    }
+    fn register(&self, handle: &Handle<StandardMaterial>, resolution: AmbientCGResolution, state: &AmbientCGState) {
+        state.0.registrations.lock().unwrap().push(AmbientCGRegistryEntry {
+            name: self.name.to_string(),
+            subfolder: self.subfolder.map(str::to_string),
+            resolution,
+            handle: handle.clone(),
+            descriptor: self.detached(state)
+        });
+    }
+    /// A copy without the borrowed `name` and `subfolder`, so it can outlive this descriptor. `channel_subfolders` and
+    /// `channel_paths` are interned. Every other field is listed, a struct update from `self` would keep its lifetime.
+    fn detached(&self, state: &AmbientCGState) -> AmbientCGMaterial<'static> {
+        let Self {
+            name: _,
+            subfolder: _,
+            channel_subfolders,
+            channel_paths,
+            resolution,
+            variant,
+            uv_scale,
+            normal_strength,
+            roughness_is_gloss,
+            occlusion_strength,
+            occlusion_in_color,
+            double_sided,
+            flip_normal_y,
+            mirror_u,
+            mirror_v,
+            specular_transmission,
+            linear_color,
+            tiling,
+            normal_convention,
+            base_color,
+            perceptual_roughness,
+            metallic,
+            reflectance,
+            metalness_from_alpha,
+            roughness_from_color,
+            cull_mode,
+            depth_bias,
+            fog_enabled
+        } = self.clone();
+        AmbientCGMaterial {
+            name: "",
+            subfolder: None,
+            channel_subfolders: intern_channel_strs(channel_subfolders, state),
+            channel_paths: intern_channel_strs(channel_paths, state),
+            resolution,
+            variant,
+            uv_scale,
+            normal_strength,
+            roughness_is_gloss,
+            occlusion_strength,
+            occlusion_in_color,
+            double_sided,
+            flip_normal_y,
+            mirror_u,
+            mirror_v,
+            specular_transmission,
+            linear_color,
+            tiling,
+            normal_convention,
+            base_color,
+            perceptual_roughness,
+            metallic,
+            reflectance,
+            metalness_from_alpha,
+            roughness_from_color,
+            cull_mode,
+            depth_bias,
+            fog_enabled
+        }
+    }
+    /// Describes the `StandardMaterial` that `load` would build, its scalars and where each texture would come from,
+    /// without decoding anything or adding to `Assets`. Only existence checks touch the disk, plus image headers when
+    /// `max_texture_size` is set, so it suits editor tooling and snapshot tests. Serializable with the `serde` feature.
+    pub fn describe(&self, config: &AmbientCGConfig) -> Result<StandardMaterialDescription, AmbientCGImportError> {
+        let material = self.negotiated(config)?;
+        let config = &material.sampler_config(config);
+        let folder_name = material.folder_name();
+        let paths = material.texture_paths(config);
+        if let Some(channel) = config.required_channels.iter().find(|channel| find_texture(paths.get(**channel), config).is_none()) {
+            return Err(AmbientCGImportError(AmbientCGErrorType::MissingChannel(*channel)));
+        }
+        let generated = |channel: &str, sources: Vec<PathBuf>| TextureSource::Generated {
+            label: format!("{}#{}", folder_name, channel),
+            sources
+        };
+        // maps over `max_texture_size` are downsampled on the CPU like any other generated image
+        let file = |path: PathBuf, channel: TextureChannel| if is_oversized(&path, config) {
+            generated(channel.suffix(), vec![path])
+        } else {
+            TextureSource::File(path)
+        };
 
-    assert_eq!(roughness.width(), metallic.width(), "Images must have the same width");
-    assert_eq!(roughness.height(), metallic.height(), "Images must have the same height");
+        let occlusion_path = find_texture(&paths.ambient_occlusion, config).filter(|_| !material.occlusion_in_color);
+        let metalness_path = find_texture(&paths.metalness, config);
+        let roughness_path = find_texture(&paths.roughness, config);
+        let alpha_metalness_path = match (&metalness_path, material.metalness_from_alpha) {
+            (None, Some(channel)) => find_texture(paths.get(channel), config),
+            _ => None
+        };
+        let metalness_path = metalness_path.or(alpha_metalness_path.clone());
+        let color_roughness_path = match &roughness_path {
+            None if material.roughness_from_color => find_texture(&paths.color, config),
+            _ => None
+        };
+        let roughness_path = roughness_path.or(color_roughness_path.clone());
+        let packs_occlusion = config.pack_occlusion && metalness_path.is_some() && roughness_path.is_some();
+        let has_metalness = metalness_path.is_some();
+        let has_roughness = roughness_path.is_some();
 
-    let (width, height) = (roughness.width(), roughness.height());
-    
-    let mut metallic_roughness = RgbImage::new(width, height);
+        let metallic_roughness = match (metalness_path, roughness_path) {
+            (Some(metalness), Some(roughness)) => {
+                let sources = [Some(roughness), Some(metalness), occlusion_path.clone().filter(|_| packs_occlusion)];
+                Some(generated("MetallicRoughness", sources.into_iter().flatten().collect()))
+            }
+            (Some(path), None) if alpha_metalness_path.is_some() => Some(generated("MetallicRoughness", vec![path])),
+            (None, Some(path)) if color_roughness_path.is_some() => Some(generated("MetallicRoughness", vec![path])),
+            (Some(path), None) => Some(file(path, TextureChannel::Metalness)),
+            (None, Some(path)) => Some(file(path, TextureChannel::Roughness)),
+            (None, None) => None
+        };
+        let occlusion = match (occlusion_path, material.occlusion_strength) {
+            _ if packs_occlusion => metallic_roughness.clone(),
+            (Some(path), Some(_)) => Some(generated(TextureChannel::AmbientOcclusion.suffix(), vec![path])),
+            (Some(path), None) => Some(file(path, TextureChannel::AmbientOcclusion)),
+            (None, _) => None
+        };
+        let normal_map = match material.find_normal_map(&paths, config) {
+            Some((path, channel)) if material.normal_adjustments(channel, config).is_identity() => Some(file(path, channel)),
+            Some((path, _)) => Some(generated(TextureChannel::NormalGL.suffix(), vec![path])),
+            None if config.flat_normal_fallback => Some(generated("FlatNormal", Vec::new())),
+            None => None
+        };
 
-    for (x, y, pixel) in metallic_roughness.enumerate_pixels_mut() {
-        let roughness = roughness.get_pixel(x, y)[0];
-        let metallic = metallic.get_pixel(x, y)[0];
+        let uv_scale = material.effective_uv_scale(config);
+        let textures = MaterialTextures { has_metalness, has_roughness, ..default() };
+        let standard = material.configured_material(textures, uv_scale, config);
+        let transform = standard.uv_transform;
+        Ok(StandardMaterialDescription {
+            resolution: material.resolution,
+            base_color: standard.base_color.to_linear().to_f32_array(),
+            metallic: standard.metallic,
+            perceptual_roughness: standard.perceptual_roughness,
+            reflectance: standard.reflectance,
+            specular_transmission: standard.specular_transmission,
+            double_sided: standard.double_sided,
+            uv_transform: [
+                transform.matrix2.x_axis.x,
+                transform.matrix2.x_axis.y,
+                transform.matrix2.y_axis.x,
+                transform.matrix2.y_axis.y,
+                transform.translation.x,
+                transform.translation.y
+            ],
+            base_color_texture: find_texture(&paths.color, config).map(|path| file(path, TextureChannel::Color)),
+            metallic_roughness_texture: metallic_roughness,
+            normal_map_texture: normal_map,
+            occlusion_texture: occlusion,
+            thickness_texture: find_texture(&paths.displacement, config).map(|path| file(path, TextureChannel::Displacement)),
+            specular_transmission_texture: find_texture(&paths.transmission, config).map(|path| file(path, TextureChannel::Transmission))
+        })
+    }
+    /// Builds the path of every channel from the AmbientCG naming convention,
+    /// `{materials_path}/{subfolder}/{name}_{resolution}-{variant}/{name}_{resolution}-{variant}_{channel}.{jpg|png}`.
+    /// Nothing is read from disk, so the resolution is used as is and the extension follows the variant. Channels
+    /// listed in `channel_paths` take their path as is.
+    pub fn texture_paths(&self, config: &AmbientCGConfig) -> MaterialTexturePaths {
+        let folder_name = self.folder_name();
+        let folder = self.materials_path(config).join(&folder_name);
+        let path = |channel: TextureChannel| {
+            if let Some((_, path)) = self.channel_paths.iter().find(|(overridden, _)| *overridden == channel) {
+                return PathBuf::from(path);
+            }
+            let file_name = format!("{}_{}.{}", folder_name, channel.suffix(), self.variant.extension());
+            match self.channel_subfolders.iter().find(|(overridden, _)| *overridden == channel) {
+                Some((_, subfolder)) => config.materials_path.join(subfolder).join(&folder_name).join(file_name),
+                None => folder.join(file_name)
+            }
+        };
+        MaterialTexturePaths {
+            ambient_occlusion: path(TextureChannel::AmbientOcclusion),
+            color: path(TextureChannel::Color),
+            displacement: path(TextureChannel::Displacement),
+            metalness: path(TextureChannel::Metalness),
+            normal_gl: path(TextureChannel::NormalGL),
+            normal_dx: path(TextureChannel::NormalDX),
+            roughness: path(TextureChannel::Roughness),
+            transmission: path(TextureChannel::Transmission),
+            folder
+        }
+    }
+    /// Folder holding this material's resolution folders, `materials_path` joined with the subfolder.
+    fn materials_path(&self, config: &AmbientCGConfig) -> PathBuf {
+        let mut materials_path = config.materials_path.clone();
+        if let Some(subfolder) = &self.subfolder {
+            materials_path.push(subfolder);
+            // a subfolder naming the extracted material folder itself would be joined with it again
+            if materials_path.file_name().and_then(OsStr::to_str).and_then(|folder_name| self.parse_folder_name(folder_name)).is_some() {
+                debug!("AmbientCG material {} has its own folder as subfolder \"{}\", using its parent", self.name, subfolder);
+                materials_path.pop();
+            }
+        }
+        materials_path
+    }
+    /// Resolution of `folder_name` if it is one of this material's `{name}_{resolution}-{variant}` folders.
+    fn parse_folder_name(&self, folder_name: &str) -> Option<AmbientCGResolution> {
+        folder_name.strip_prefix(self.name)?.strip_prefix('_')?.strip_suffix(self.variant.to_string().as_str())?.strip_suffix('-')?.parse().ok()
+    }
+    /// Displaces the vertices of `mesh` along their normals by this material's `_Displacement` map, so geometry gains
+    /// real relief instead of shader parallax. Heights are sampled bilinearly at the vertex UVs with the material's
+    /// UV transform applied, and a height of 1.0 moves a vertex by `strength`.
+    ///
+    /// Only existing vertices move, so the mesh needs enough subdivisions for the map's detail (e.g.
+    /// `Plane3d::default().mesh().subdivisions(256)`). Normals are left as they are, recompute them with
+    /// `Mesh::compute_normals` if lighting should follow the new shape.
+    pub fn displace_mesh(&self, mesh: &mut Mesh, strength: f32, config: &AmbientCGConfig) -> Result<(), AmbientCGImportError> {
+        let material = self.negotiated(config)?;
+        let Some(displacement_path) = find_texture(&material.texture_paths(config).displacement, config) else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::NotFound));
+        };
+        // 16 bit keeps the precision of AmbientCG's 16 bit PNG height maps, 8 bit JPGs are widened losslessly
+        let height = load_image(&displacement_path, TextureChannel::Displacement, config)?.to_luma16();
 
-        // Set the new pixel's color (R = 0, G = roughness, B = metallic)
-        let color = [0, roughness, metallic];
+        let uv_scale = self.effective_uv_scale(config);
+        let uv_transform = self.configured_material(MaterialTextures::default(), uv_scale, config).uv_transform;
+        let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL).cloned() else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::InvalidMesh("normals")));
+        };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0).cloned() else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::InvalidMesh("UVs")));
+        };
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::InvalidMesh("positions")));
+        };
 
-        pixel.0 = color;
+        for ((position, normal), uv) in positions.iter_mut().zip(normals).zip(uvs) {
+            let offset = Vec3::from(normal) * sample_height(&height, uv_transform.transform_point2(Vec2::from(uv))) * strength;
+            *position = (Vec3::from(*position) + offset).to_array();
+        }
+        Ok(())
+    }
+    /// Applies the configured resolution policy, returning the material at the resolution that will be loaded.
+    fn negotiated(&self, config: &AmbientCGConfig) -> Result<Self, AmbientCGImportError> {
+        let material_path = self.materials_path(config);
+        match config.resolution_policy {
+            AmbientCGResolutionPolicy::Negotiate if config.resolution_negotiation => {
+                let resolution = config.resolution_negotiator.negotiate(self, config)?;
+                Ok(Self { resolution, ..self.clone() })
+            }
+            AmbientCGResolutionPolicy::Negotiate => Ok(self.clone()),
+            AmbientCGResolutionPolicy::Exact => {
+                if !resource_exists(&material_path.join(self.folder_name()), config) {
+                    return Err(self.missing_files(config));
+                }
+                Ok(self.clone())
+            }
+        }
+    }
+    /// `MissingFiles` listing where every channel was expected at the requested resolution, for negotiators that
+    /// found nothing to load. `NotFound` for HDRIs, which have no channels.
+    pub fn missing_files(&self, config: &AmbientCGConfig) -> AmbientCGImportError {
+        if self.variant == AmbientCGVariant::Hdr {
+            return AmbientCGImportError(AmbientCGErrorType::NotFound);
+        }
+        let paths = self.texture_paths(config);
+        let files = TextureChannel::ALL.into_iter().map(|channel| (channel, paths.get(channel).clone())).collect();
+        AmbientCGImportError(AmbientCGErrorType::MissingFiles(files))
+    }
+    /// `load_textures` for a new material, counted in `AmbientCGLoadSummary`. `None` when `config.headless` is set,
+    /// in which case callers add no material either.
+    fn load_counted(&self, asset_server: &AssetServer, config: &AmbientCGConfig, state: &AmbientCGState) -> Result<Option<MaterialTextures>, AmbientCGImportError> {
+        if config.headless {
+            return Ok(None);
+        }
+        let result = self.load_textures(asset_server, config, state);
+        let loaded = result.as_ref().ok().map(|textures| textures.resolution);
+        state.0.summary.lock().unwrap().record(self.resolution, loaded);
+        result.map(Some)
     }
+    /// Loads this material's textures without assembling a `StandardMaterial`, e.g. to feed a custom material or a UI
+    /// preview. Returns no textures when `config.headless` is set.
+    pub fn load_textures(&self, asset_server: &AssetServer, config: &AmbientCGConfig, state: &AmbientCGState) -> Result<MaterialTextures, AmbientCGImportError> {
+        if config.headless {
+            return Ok(MaterialTextures::default());
+        }
+        let ambient_cg_material = self.negotiated(config)?;
 
-    Image::from_dynamic(
-        DynamicImage::ImageRgb8(metallic_roughness),
-        false,
-        RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD
-    )
-}
+        let config = &ambient_cg_material.sampler_config(config);
+
+        let constructed_material_name = ambient_cg_material.folder_name();
+        let paths = ambient_cg_material.texture_paths(config);
+        if let Some(channel) = config.required_channels.iter().find(|channel| find_texture(paths.get(**channel), config).is_none()) {
+            return Err(AmbientCGImportError(AmbientCGErrorType::MissingChannel(*channel)));
+        }
+        if config.mixed_resolutions != MixedResolutions::Allow {
+            if let Some((channel, path)) = mixed_resolution_channel(&paths, config) {
+                if config.mixed_resolutions == MixedResolutions::Deny {
+                    return Err(AmbientCGImportError(AmbientCGErrorType::MixedResolution(channel, path)));
+                }
+                warn!("AmbientCG material {} mixes resolutions, {} at {} differs from its other maps", constructed_material_name, channel.suffix(), path.display());
+            }
+        }
+
+        let occlusion_path = find_texture(&paths.ambient_occlusion, config)
+            .filter(|_| !ambient_cg_material.occlusion_in_color);
+        let base_color_path = find_texture(&paths.color, config);
+        let thickness_path = find_texture(&paths.displacement, config);
+        let transmission_path = find_texture(&paths.transmission, config);
+        let metallic_texture_path = find_texture(&paths.metalness, config);
+        let normal_map = ambient_cg_material.find_normal_map(&paths, config);
+        let roughness_texture_path = find_texture(&paths.roughness, config);
+
+        let channel_settings = |channel: TextureChannel| {
+            let settings = ambient_cg_material.loader_settings(channel, config);
+            move |s: &mut ImageLoaderSettings| *s = settings.clone()
+        };
+        // maps over `max_texture_size` are downsampled on the CPU, the others load through the asset server as they are
+        let load_channel = |path: PathBuf, channel: TextureChannel| {
+            // a map failing to decode here is left to the asset server, which reports it like any other map
+            match is_oversized(&path, config).then(|| load_image(&path, channel, config).ok()).flatten() {
+                Some(image) => asset_server.add(labeled_image(
+                    generated_image(image, ambient_cg_material.is_srgb(channel)),
+                    &constructed_material_name,
+                    channel.suffix(),
+                    config, state
+                )),
+                None => load_texture(asset_server, path, channel_settings(channel), config)
+            }
+        };
 
-fn load_grayscale_image(path: &PathBuf) -> DynamicImage {
-    let image = ImageReader::open(path).expect("Could not load image").decode();
-    image.expect("Could not determine file encoding").grayscale()
-}
\ No newline at end of file
+        // metalness packed into another map's alpha stands in for a missing `_Metalness` file
+        let mut alpha_metalness = match (&metallic_texture_path, ambient_cg_material.metalness_from_alpha) {
+            (None, Some(channel)) => match find_texture(paths.get(channel), config) {
+                Some(path) => lenient(load_alpha_image(&path, channel, config), config)?.flatten(),
+                None => None
+            },
+            _ => None
+        };
+        // the color map's luminance stands in for a missing roughness map, see `roughness_from_color`
+        let mut color_roughness = match (&roughness_texture_path, &base_color_path) {
+            (None, Some(path)) if ambient_cg_material.roughness_from_color => {
+                lenient(load_grayscale_image(path, TextureChannel::Color, config, state), config)?
+            }
+            _ => None
+        };
+        let has_metalness_source = metallic_texture_path.is_some() || alpha_metalness.is_some();
+        let has_roughness_source = roughness_texture_path.is_some() || color_roughness.is_some();
+        let packs_occlusion = config.pack_occlusion && has_metalness_source && has_roughness_source;
+        let (packed_occlusion_path, occlusion_path) = if packs_occlusion {
+            (occlusion_path, None)
+        } else {
+            (None, occlusion_path)
+        };
+
+        let occlusion_texture: Option<Handle<Image>> = match (occlusion_path, ambient_cg_material.occlusion_strength) {
+            (Some(path), Some(strength)) => lenient(create_occlusion_image(path, strength, config, state), config)?.map(|image| asset_server.add(labeled_image(
+                image,
+                &constructed_material_name,
+                TextureChannel::AmbientOcclusion.suffix(),
+                config, state
+            ))),
+            (Some(path), None) => Some(load_channel(path, TextureChannel::AmbientOcclusion)),
+            (None, _) => None
+        };
+        let base_color_texture: Option<Handle<Image>> = base_color_path.map(|path| load_channel(path, TextureChannel::Color));
+        let thickness_texture: Option<Handle<Image>> = thickness_path.map(|path| load_channel(path, TextureChannel::Displacement));
+        let transmission_texture: Option<Handle<Image>> = transmission_path.map(|path| load_channel(path, TextureChannel::Transmission));
+        let normal_map_texture: Option<Handle<Image>> = match normal_map {
+            Some((path, channel)) if ambient_cg_material.normal_adjustments(channel, config).is_identity() => {
+                Some(load_channel(path, channel))
+            }
+            Some((path, channel)) => lenient(create_normal_image(path, channel, ambient_cg_material.normal_adjustments(channel, config), config), config)?
+                .map(|image| asset_server.add(labeled_image(image, &constructed_material_name, TextureChannel::NormalGL.suffix(), config, state))),
+            None => None
+        };
+
+        // a single map drives both channels, loaded as is from its file or added from the generated image
+        let lone_map = |path: Option<PathBuf>, channel: TextureChannel, image: DynamicImage| match path {
+            Some(path) => load_channel(path, channel),
+            None => asset_server.add(labeled_image(generated_image(image, false), &constructed_material_name, "MetallicRoughness", config, state))
+        };
+        let mut has_metalness = has_metalness_source;
+        let mut has_roughness = has_roughness_source;
+        let (metallic_roughness_texture, occlusion_packed) = match (metallic_texture_path, roughness_texture_path) {
+            (metallic_texture_path, roughness_texture_path) if has_metalness_source && has_roughness_source => {
+                let roughness = match &roughness_texture_path {
+                    Some(path) => lenient(load_grayscale_image(path, TextureChannel::Roughness, config, state), config)?,
+                    None => color_roughness.take()
+                };
+                let metallic = match &metallic_texture_path {
+                    Some(path) => lenient(load_grayscale_image(path, TextureChannel::Metalness, config, state), config)?,
+                    None => alpha_metalness.take()
+                };
+                let packed_occlusion = match &packed_occlusion_path {
+                    Some(path) => lenient(load_grayscale_image(path, TextureChannel::AmbientOcclusion, config, state), config)?,
+                    None => None
+                };
+                has_metalness = metallic.is_some();
+                has_roughness = roughness.is_some();
+                match (metallic, roughness) {
+                    (Some(metallic), Some(roughness)) => {
+                        let occlusion_packed = packed_occlusion.is_some();
+                        let inputs = MergeInputs::new(
+                            roughness,
+                            metallic,
+                            packed_occlusion,
+                            ambient_cg_material.occlusion_strength,
+                            ambient_cg_material.roughness_is_gloss,
+                            config.channel_layout,
+                            config
+                        )?;
+                        (Some(add_merged_image(asset_server, inputs, &constructed_material_name, config, state)), occlusion_packed)
+                    }
+                    // lenient decoding dropped one source of the merge, the other map drives its channel on its own
+                    (Some(metallic), None) => (Some(lone_map(metallic_texture_path, TextureChannel::Metalness, metallic)), false),
+                    (None, Some(roughness)) => (Some(lone_map(roughness_texture_path, TextureChannel::Roughness, roughness)), false),
+                    (None, None) => (None, false)
+                }
+            }
+            // both paths exist only with both sources, which the arm above takes
+            (Some(path), _) => (Some(load_channel(path, TextureChannel::Metalness)), false),
+            (_, Some(path)) => (Some(load_channel(path, TextureChannel::Roughness)), false),
+            (None, None) => {
+                let lone = alpha_metalness.take().or_else(|| color_roughness.take());
+                (lone.map(|image| lone_map(None, TextureChannel::Metalness, image)), false)
+            }
+        };
+        // bevy samples occlusion from the red channel, which now holds the packed map
+        let occlusion_texture = if occlusion_packed {
+            metallic_roughness_texture.clone()
+        } else {
+            occlusion_texture
+        };
+
+        let mut textures = MaterialTextures {
+            base_color: base_color_texture,
+            metallic_roughness: metallic_roughness_texture,
+            normal_map: normal_map_texture,
+            occlusion: occlusion_texture,
+            thickness: thickness_texture,
+            specular_transmission: transmission_texture,
+            has_metalness,
+            has_roughness,
+            resolution: ambient_cg_material.resolution
+        };
+        if !textures.has_surface() {
+            warn!("AmbientCG material {} in {} has neither a color nor a normal map", constructed_material_name, paths.folder.display());
+        }
+        if config.flat_normal_fallback && textures.normal_map.is_none() {
+            textures.normal_map = Some(asset_server.add(labeled_image(flat_normal_image(), &constructed_material_name, "FlatNormal", config, state)));
+        }
+        Ok(textures)
+    }
+    /// Whether `channel` holds color rather than data and is loaded as sRGB.
+    fn is_srgb(&self, channel: TextureChannel) -> bool {
+        channel == TextureChannel::Color && !self.linear_color
+    }
+    fn loader_settings(&self, channel: TextureChannel, config: &AmbientCGConfig) -> ImageLoaderSettings {
+        ImageLoaderSettings {
+            sampler: channel_sampler(channel, &self.sampler_config(config)),
+            is_srgb: self.is_srgb(channel),
+            ..default()
+        }
+    }
+    /// `config` with this material's sampler overrides applied, for the samplers of its textures.
+    fn sampler_config(&self, config: &AmbientCGConfig) -> AmbientCGConfig {
+        let mut config = config.clone();
+        if !self.tiling {
+            config.address_mode = ImageAddressMode::ClampToEdge;
+        }
+        config
+    }
+    /// Adjustments turning the normal map of `channel` into the one this material needs.
+    fn normal_adjustments(&self, channel: TextureChannel, config: &AmbientCGConfig) -> NormalAdjustments {
+        // DirectX maps point green the other way
+        let directx = channel == TextureChannel::NormalDX;
+        NormalAdjustments {
+            strength: self.normal_strength.unwrap_or(1.0),
+            // tangents come from the mesh and ignore uv_transform, so a mirrored lookup needs the mirrored normal too
+            flip_x: self.mirror_u,
+            flip_y: (self.flip_normal_y != (self.mirror_v != config.flip_v)) != directx
+        }
+    }
+    /// Normal map channels to probe in order of preference.
+    fn normal_channels(&self) -> &'static [TextureChannel] {
+        match self.normal_convention {
+            NormalConvention::Auto => &[TextureChannel::NormalGL, TextureChannel::NormalDX],
+            NormalConvention::OpenGL => &[TextureChannel::NormalGL],
+            NormalConvention::DirectX => &[TextureChannel::NormalDX],
+        }
+    }
+    /// Finds the normal map following `normal_convention`, with the channel it was found as.
+    fn find_normal_map(&self, paths: &MaterialTexturePaths, config: &AmbientCGConfig) -> Option<(PathBuf, TextureChannel)> {
+        let (path, channel) = self.normal_channels()
+            .iter()
+            .find_map(|channel| find_texture(paths.get(*channel), config).map(|path| (path, *channel)))?;
+        debug!("Detected {} normal map for {}", channel.suffix(), self.folder_name());
+        Some((path, channel))
+    }
+    /// `standard_material` with the app wide settings of `config` applied on top.
+    fn configured_material(&self, textures: MaterialTextures, uv_scale: Vec2, config: &AmbientCGConfig) -> StandardMaterial {
+        let mut material = self.standard_material(textures, uv_scale);
+        if config.flip_v {
+            // v' = 1 - v, applied to the mesh UVs before the material's own scale
+            material.uv_transform *= Affine2::from_scale_angle_translation(Vec2::new(1.0, -1.0), 0.0, Vec2::Y);
+        }
+        material
+    }
+    fn standard_material(&self, textures: MaterialTextures, uv_scale: Vec2) -> StandardMaterial {
+        let defaults = StandardMaterial::default();
+        StandardMaterial {
+            base_color: self.base_color.unwrap_or(defaults.base_color),
+            base_color_texture: textures.base_color,
+            metallic_roughness_texture: textures.metallic_roughness,
+            // The scalars multiply the texture, so they only go to 1.0 when a map is there to drive the channel.
+            // Otherwise a material without a metalness map would render as polished metal.
+            metallic: self.metallic.unwrap_or(if textures.has_metalness { 1.0 } else { 0.0 }),
+            normal_map_texture: textures.normal_map,
+            occlusion_texture: textures.occlusion,
+            perceptual_roughness: self.perceptual_roughness.unwrap_or(
+                if textures.has_roughness { 1.0 } else { defaults.perceptual_roughness }
+            ),
+            thickness_texture: textures.thickness,
+            // like metallic above, the scalar multiplies the map and only defaults to 1.0 when a map is present
+            specular_transmission: self.specular_transmission.unwrap_or(
+                if textures.specular_transmission.is_some() { 1.0 } else { defaults.specular_transmission }
+            ),
+            specular_transmission_texture: textures.specular_transmission,
+            reflectance: self.reflectance.unwrap_or(defaults.reflectance),
+            double_sided: self.double_sided,
+            cull_mode: self.cull_mode.unwrap_or(if self.double_sided { None } else { defaults.cull_mode }),
+            depth_bias: self.depth_bias.unwrap_or(defaults.depth_bias),
+            fog_enabled: self.fog_enabled.unwrap_or(defaults.fog_enabled),
+            uv_transform: (|| {
+                let mirror = Vec2::new(
+                    if self.mirror_u { -1.0 } else { 1.0 },
+                    if self.mirror_v { -1.0 } else { 1.0 }
+                );
+                if uv_scale == Vec2::ZERO {
+                    return Affine2::from_scale(mirror);
+                }
+                Affine2::from_scale(uv_scale * mirror)
+            })(),
+            ..defaults
+        }
+    }
+}
+
+/// A group of related materials sharing a base name and differing by suffix, e.g. `Bricks075A` and `Bricks075B`.
+#[derive(Clone, Debug)]
+pub struct AmbientCGMaterialSet<'a> {
+    pub base_name: &'a str,
+    /// Suffixes appended to `base_name`, e.g. `&["A", "B"]`.
+    pub variants: &'a [&'a str],
+    /// Settings shared by every variant, its `name` is ignored.
+    pub template: AmbientCGMaterial<'a>
+}
+
+impl AmbientCGMaterialSet<'_> {
+    /// Full material name of the variant at `index`, `None` if out of range.
+    pub fn variant_name(&self, index: usize) -> Option<String> {
+        self.variants.get(index).map(|variant| format!("{}{}", self.base_name, variant))
+    }
+    /// Loads the variant at `index`, panicking if it is out of range or fails to load. Use `try_load_variant` to handle
+    /// those errors instead.
+    pub fn load_variant(
+        &self,
+        index: usize,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Handle<StandardMaterial> {
+        expect_loaded(self.try_load_variant(index, asset_server, materials, config, state))
+    }
+    /// Loads the variant at `index`, an out of range index is `NotFound`.
+    pub fn try_load_variant(
+        &self,
+        index: usize,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+        let Some(name) = self.variant_name(index) else {
+            return Err(AmbientCGImportError(AmbientCGErrorType::NotFound));
+        };
+        AmbientCGMaterial {
+            name: &name,
+            ..self.template.clone()
+        }.try_load(asset_server, materials, config, state)
+    }
+}
+
+/// Replaces the material of `entity` and of every descendant that has a `MeshMaterial3d<StandardMaterial>`, e.g. to
+/// override all materials of a spawned glTF scene. Queue it with `commands.queue(...)`.
+///
+/// A scene's hierarchy only exists once it has finished spawning, so for scenes queue this from an observer of
+/// `SceneInstanceReady`.
+pub fn apply_ambient_cg_material(entity: Entity, material: Handle<StandardMaterial>) -> impl Command {
+    move |world: &mut World| {
+        let mut pending = vec![entity];
+        while let Some(entity) = pending.pop() {
+            let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            if entity_mut.contains::<MeshMaterial3d<StandardMaterial>>() {
+                entity_mut.insert(MeshMaterial3d(material.clone()));
+            }
+            if let Some(children) = entity_mut.get::<Children>() {
+                pending.extend_from_slice(children);
+            }
+        }
+    }
+}
+
+/// Spawns meshes textured with an AmbientCG material in one call.
+pub trait AmbientCGCommandsExt {
+    /// Loads `material` and spawns `mesh` with it at `transform`, panicking like `AmbientCGMaterial::load` if the
+    /// material cannot be loaded.
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_ambient_cg(
+        &mut self,
+        mesh: Handle<Mesh>,
+        material: &AmbientCGMaterial,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        transform: Transform,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> EntityCommands<'_>;
+}
+
+impl AmbientCGCommandsExt for Commands<'_, '_> {
+    fn spawn_ambient_cg(
+        &mut self,
+        mesh: Handle<Mesh>,
+        material: &AmbientCGMaterial,
+        asset_server: &Res<'_, AssetServer>,
+        materials: &mut ResMut<'_, Assets<StandardMaterial>>,
+        transform: Transform,
+        config: &AmbientCGConfig,
+        state: &AmbientCGState
+    ) -> EntityCommands<'_> {
+        self.spawn((Mesh3d(mesh), material.load_component(asset_server, materials, config, state), transform))
+    }
+}
+
+/// Changes baked into a normal map that is synthesized on the CPU instead of loaded as is.
+#[derive(Clone, Copy)]
+struct NormalAdjustments {
+    strength: f32,
+    flip_x: bool,
+    flip_y: bool
+}
+
+impl NormalAdjustments {
+    fn is_identity(&self) -> bool {
+        self.strength == 1.0 && !self.flip_x && !self.flip_y
+    }
+}
+
+#[cfg(feature = "export")]
+impl AmbientCGMaterial<'_> {
+    /// Bakes this material into `out_dir` for use in other tools and returns the path of the written glTF JSON.
+    ///
+    /// The `_Roughness`, `_Metalness` and `_AmbientOcclusion` maps are packed into a glTF ORM texture
+    /// (`{folder}_ORM.png`, R = occlusion, G = roughness, B = metalness) the same way materials are merged at load
+    /// time, color and normal maps are copied as they are. The JSON holds `images`, `textures` and `materials` arrays
+    /// referencing those files, to be merged into a glTF document. Only files on disk can be exported, not embedded
+    /// textures.
+    pub fn export_gltf(&self, out_dir: &Path, config: &AmbientCGConfig) -> Result<PathBuf, AmbientCGImportError> {
+        let material = self.negotiated(config)?;
+        let name = material.folder_name();
+        let paths = material.texture_paths(config);
+        std::fs::create_dir_all(out_dir)
+            .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Io(out_dir.to_path_buf(), error)))?;
+
+        let mut images = Vec::new();
+        let base_color = find_texture(&paths.color, config)
+            .map(|path| export_copy(&path, out_dir, &mut images, config))
+            .transpose()?;
+        let normal = find_texture(&paths.normal_gl, config)
+            .map(|path| export_copy(&path, out_dir, &mut images, config))
+            .transpose()?;
+        let occlusion_path = find_texture(&paths.ambient_occlusion, config).filter(|_| !self.occlusion_in_color);
+        let metalness_path = find_texture(&paths.metalness, config);
+        let has_metalness = metalness_path.is_some();
+        let (metallic_roughness, occlusion) = match (metalness_path, find_texture(&paths.roughness, config)) {
+            (Some(metalness_path), Some(roughness_path)) => {
+                let orm = load_merge_inputs(
+                    roughness_path,
+                    metalness_path,
+                    occlusion_path.clone(),
+                    self.occlusion_strength,
+                    self.roughness_is_gloss,
+                    // glTF expects its own layout whatever the runtime packing is
+                    ChannelLayout::GLTF,
+                    config,
+                    &AmbientCGState::default()
+                )?.merge();
+                let file_name = format!("{}_ORM.png", name);
+                let orm_path = out_dir.join(&file_name);
+                orm.save_with_format(&orm_path, image::ImageFormat::Png)
+                    .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Encode(orm_path, error)))?;
+                images.push(file_name);
+                let index = images.len() - 1;
+                (Some(index), occlusion_path.map(|_| index))
+            }
+            // glTF samples metalness and roughness from one texture, so a lone map is left to the scalar factors
+            _ => (None, occlusion_path.map(|path| export_copy(&path, out_dir, &mut images, config)).transpose()?)
+        };
+
+        let texture_ref = |index: Option<usize>, key: &str| index.map(|index| format!("\"{}\": {{ \"index\": {} }}", key, index));
+        let pbr = [
+            texture_ref(base_color, "baseColorTexture"),
+            texture_ref(metallic_roughness, "metallicRoughnessTexture"),
+            Some(format!("\"metallicFactor\": {:.1}", if has_metalness { 1.0 } else { 0.0 })),
+            Some(format!("\"roughnessFactor\": {:.1}", 1.0))
+        ];
+        let material_fields = [
+            Some(format!("\"name\": \"{}\"", name)),
+            Some(format!("\"pbrMetallicRoughness\": {{ {} }}", pbr.into_iter().flatten().collect::<Vec<_>>().join(", "))),
+            texture_ref(normal, "normalTexture"),
+            texture_ref(occlusion, "occlusionTexture"),
+            Some(format!("\"doubleSided\": {}", self.double_sided))
+        ];
+        let json = format!(
+            "{{\n  \"images\": [{}],\n  \"textures\": [{}],\n  \"materials\": [{{ {} }}]\n}}\n",
+            images.iter().map(|uri| format!("{{ \"uri\": \"{}\" }}", uri)).collect::<Vec<_>>().join(", "),
+            (0..images.len()).map(|index| format!("{{ \"source\": {} }}", index)).collect::<Vec<_>>().join(", "),
+            material_fields.into_iter().flatten().collect::<Vec<_>>().join(", ")
+        );
+
+        let json_path = out_dir.join(format!("{}.gltf.json", name));
+        std::fs::write(&json_path, json)
+            .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Io(json_path.clone(), error)))?;
+        Ok(json_path)
+    }
+}
+
+/// Copies a source texture into the export folder and returns its index in `images`.
+#[cfg(feature = "export")]
+fn export_copy(path: &Path, out_dir: &Path, images: &mut Vec<String>, config: &AmbientCGConfig) -> Result<usize, AmbientCGImportError> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let source = resolve_resource_path(path, config.asset_root.as_deref());
+    std::fs::copy(&source, out_dir.join(&file_name))
+        .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Io(source, error)))?;
+    images.push(file_name);
+    Ok(images.len() - 1)
+}
+
+/// What `AmbientCGMaterial::load` would build, see `AmbientCGMaterial::describe`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StandardMaterialDescription {
+    /// Resolution after negotiation.
+    pub resolution: AmbientCGResolution,
+    /// Linear RGBA.
+    pub base_color: [f32; 4],
+    pub metallic: f32,
+    pub perceptual_roughness: f32,
+    pub reflectance: f32,
+    pub specular_transmission: f32,
+    pub double_sided: bool,
+    /// `StandardMaterial::uv_transform` as its 2x2 matrix, column by column, followed by the translation.
+    pub uv_transform: [f32; 6],
+    pub base_color_texture: Option<TextureSource>,
+    pub metallic_roughness_texture: Option<TextureSource>,
+    pub normal_map_texture: Option<TextureSource>,
+    pub occlusion_texture: Option<TextureSource>,
+    pub thickness_texture: Option<TextureSource>,
+    pub specular_transmission_texture: Option<TextureSource>
+}
+
+/// Where a texture of a `StandardMaterialDescription` comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TextureSource {
+    /// Loaded as is by the asset server, relative to the assets folder.
+    File(PathBuf),
+    /// Synthesized on the CPU from `sources` and labeled `label`, e.g. `Rock023_2K-JPG#MetallicRoughness`.
+    Generated { label: String, sources: Vec<PathBuf> }
+}
+
+/// Builds a custom material from the textures of an AmbientCG material, see `AmbientCGMaterial::load_as`.
+pub trait FromAmbientCGTextures {
+    /// `textures` holds a handle per channel, with metalness and roughness already merged. `material` is the
+    /// descriptor being loaded, e.g. to read its `uv_scale`.
+    fn from_ambient_cg_textures(textures: MaterialTextures, material: &AmbientCGMaterial<'_>) -> Self;
+}
+
+/// Texture handles of a material, as assembled into a `StandardMaterial` by `load`. Each field is `None` when its
+/// map is missing from the material folder.
+#[derive(Clone, Debug, Default)]
+pub struct MaterialTextures {
+    pub base_color: Option<Handle<Image>>,
+    /// Generated from the `_Roughness` and `_Metalness` maps when both exist, otherwise whichever of them exists.
+    pub metallic_roughness: Option<Handle<Image>>,
+    pub normal_map: Option<Handle<Image>>,
+    /// The metallic-roughness texture itself when occlusion is packed into it.
+    pub occlusion: Option<Handle<Image>>,
+    /// The `_Displacement` map.
+    pub thickness: Option<Handle<Image>>,
+    pub specular_transmission: Option<Handle<Image>>,
+    pub has_metalness: bool,
+    pub has_roughness: bool,
+    /// Resolution the textures were found at, after negotiation.
+    pub resolution: AmbientCGResolution
+}
+
+impl MaterialTextures {
+    /// Whether a color or normal map was found. Displacement, occlusion or transmission alone don't describe a
+    /// surface, so such a material is as good as empty.
+    fn has_surface(&self) -> bool {
+        self.base_color.is_some() || self.normal_map.is_some()
+    }
+}
+
+/// Checks whether a file or folder exists in `config.file_system`, relative to the assets folder.
+///
+/// Embedded textures are checked first, a folder counts as present when any embedded texture lives inside it. The
+/// absolute path and the result are reported to `AmbientCGConfig::on_file_probe`.
+fn resource_exists(p: &Path, config: &AmbientCGConfig) -> bool {
+    #[cfg(feature = "embedded")]
+    let exists = config.embedded_textures.keys().any(|embedded| embedded.starts_with(p)) || config.file_system.exists(&config.file_path(p));
+    #[cfg(not(feature = "embedded"))]
+    let exists = config.file_system.exists(&config.file_path(p));
+    report_probe(p, exists, config);
+    exists
+}
+
+fn report_probe(p: &Path, exists: bool, config: &AmbientCGConfig) {
+    if let Some(on_file_probe) = config.on_file_probe {
+        on_file_probe(&resolve_resource_path(p, config.asset_root.as_deref()), exists);
+    }
+}
+
+/// First found channel that lives outside the material folder or whose pixel size differs from the first found
+/// channel's. Maps that can't be read, e.g. embedded ones, are skipped.
+fn mixed_resolution_channel(paths: &MaterialTexturePaths, config: &AmbientCGConfig) -> Option<(TextureChannel, PathBuf)> {
+    let folder_name = paths.folder.file_name();
+    let mut dimensions = None;
+    TextureChannel::ALL.into_iter()
+        .filter_map(|channel| find_texture(paths.get(channel), config).map(|path| (channel, path)))
+        .find(|(_, path)| {
+            if path.parent().and_then(Path::file_name) != folder_name {
+                return true;
+            }
+            let Some(size) = read_dimensions(path, config) else {
+                return false;
+            };
+            *dimensions.get_or_insert(size) != size
+        })
+}
+
+fn read_dimensions(path: &Path, config: &AmbientCGConfig) -> Option<(u32, u32)> {
+    let bytes = config.file_system.read(&config.file_path(path)).ok()?;
+    ImageReader::new(Cursor::new(bytes)).with_guessed_format().ok()?.into_dimensions().ok()
+}
+
+/// Repeat count tiling `mesh` `repeats` times across its largest dimension, `None` without positions or extent, or
+/// if `repeats` isn't a positive number.
+fn mesh_uv_scale(mesh: &Mesh, repeats: f32) -> Option<Vec2> {
+    if !(repeats > 0.0 && repeats.is_finite()) {
+        return None;
+    }
+    let mut extents = Vec3::from(mesh.compute_aabb()?.half_extents * 2.0).to_array();
+    extents.sort_unstable_by(|a, b| b.total_cmp(a));
+    if extents[0] <= 0.0 {
+        return None;
+    }
+    Some(world_uv_scale(Vec2::new(extents[0], extents[1]), extents[0] / repeats))
+}
+
+/// Repeat count tiling `texture_world_size` units of texture over `world_size` units of surface.
+fn world_uv_scale(world_size: Vec2, texture_world_size: f32) -> Vec2 {
+    world_size / texture_world_size
+}
+
+/// Finds a channel's file from its conventional path. Each channel probes the supported extensions on its own, so a
+/// `.png` normal map can sit next to a `.jpg` color map.
+///
+/// Files with inconsistent casing, e.g. `_color.JPG`, are found by a case-insensitive scan of the folder, so a pack
+/// that loads on a case-insensitive filesystem like macOS' loads on Linux too.
+fn find_texture(file_path: &Path, config: &AmbientCGConfig) -> Option<PathBuf> {
+    TEXTURE_EXTENSIONS
+        .iter()
+        .map(|extension| file_path.with_extension(extension))
+        .find(|path| resource_exists(path, config))
+        .or_else(|| find_texture_ignoring_case(file_path, config))
+}
+
+fn find_texture_ignoring_case(file_path: &Path, config: &AmbientCGConfig) -> Option<PathBuf> {
+    let folder = file_path.parent()?;
+    let stem = file_path.file_stem()?.to_str()?;
+    let entries = config.file_system.read_dir(&config.file_path(folder));
+    report_probe(folder, entries.is_ok(), config);
+    let found = entries
+        .ok()?
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|name| name.file_stem().and_then(OsStr::to_str).is_some_and(|found| found.eq_ignore_ascii_case(stem)))
+        .filter(|name| name.extension().and_then(OsStr::to_str).is_some_and(|extension| {
+            TEXTURE_EXTENSIONS.iter().any(|supported| supported.eq_ignore_ascii_case(extension))
+        }))
+        // read_dir has no defined order, sorting keeps the pick stable if several files match
+        .min()
+        .map(|name| folder.join(name));
+    report_probe(found.as_deref().unwrap_or(file_path), found.is_some(), config);
+    found
+}
+
+// config is only read for embedded textures
+#[cfg_attr(not(feature = "embedded"), allow(unused_variables))]
+fn load_texture(
+    asset_server: &AssetServer,
+    path: PathBuf,
+    settings: impl Fn(&mut ImageLoaderSettings) + Send + Sync + 'static,
+    config: &AmbientCGConfig
+) -> Handle<Image> {
+    #[cfg(feature = "embedded")]
+    if config.embedded_textures.contains_key(&path) {
+        return asset_server.load_with_settings(AssetPath::from(path).with_source("embedded"), settings);
+    }
+    // absolute paths come from `load_from_absolute`, which documents the unapproved path mode it needs
+    if path.is_absolute() {
+        return asset_server.load_with_settings_override(path, settings);
+    }
+    asset_server.load_with_settings(path, settings)
+}
+
+/// Resolves `p` against Bevy's default `assets` folder, absolute paths are returned as they are.
+pub(crate) fn default_resource_path(p: &Path) -> PathBuf {
+    resolve_resource_path(p, None)
+}
+
+/// Resolves `p` against `asset_root`, or Bevy's default `assets` folder when it is `None`.
+fn resolve_resource_path(p: &Path, asset_root: Option<&Path>) -> PathBuf {
+    if p.is_absolute() {
+        return p.to_path_buf();
+    }
+    let mut path = FileAssetReader::get_base_path();
+    // pushing an absolute root replaces the base path, as it does for `AssetPlugin::file_path`
+    path.push(asset_root.unwrap_or(Path::new("assets")));
+    path.push(p);
+    path
+}
+
+/// Collects the name, resolution, variant and subfolder, relative to the materials path, of every material folder
+/// found through `config.file_system`.
+fn discover_materials(
+    directory: &Path,
+    subfolder: &Path,
+    config: &AmbientCGConfig,
+    discovered: &mut Vec<(String, AmbientCGResolution, AmbientCGVariant, PathBuf)>
+) {
+    let Ok(entries) = config.file_system.read_dir(&config.file_path(directory)) else {
+        return;
+    };
+    for entry in entries {
+        let path = directory.join(&entry);
+        let Some(folder_name) = entry.to_str() else {
+            continue;
+        };
+        // files can't be listed, which is how the file system tells them from folders
+        if folder_name == ".cache" || config.file_system.read_dir(&config.file_path(&path)).is_err() {
+            continue;
+        }
+        match parse_material_folder_name(folder_name) {
+            Some((name, resolution, variant)) => discovered.push((name, resolution, variant, subfolder.to_path_buf())),
+            None => discover_materials(&path, &subfolder.join(folder_name), config, discovered)
+        }
+    }
+}
+
+/// Splits a `{name}_{resolution}-{variant}` folder name back into its name, resolution and variant.
+fn parse_material_folder_name(folder_name: &str) -> Option<(String, AmbientCGResolution, AmbientCGVariant)> {
+    let (folder_name, variant) = AmbientCGVariant::strip_suffix(folder_name)?;
+    let (name, resolution) = folder_name.rsplit_once('_')?;
+    Some((name.to_string(), resolution.parse().ok()?, variant))
+}
+
+fn repeat_sampler(config: &AmbientCGConfig) -> ImageSampler {
+    ImageSampler::Descriptor(ImageSamplerDescriptor {
+        // rewriting mode to repeat image,
+        address_mode_u: config.address_mode,
+        address_mode_v: config.address_mode,
+        lod_min_clamp: config.lod_min_clamp,
+        lod_max_clamp: config.lod_max_clamp,
+        ..default()
+    })
+}
+
+/// The sampler of `channel`, its entry in `AmbientCGConfig::channel_samplers` or the default one.
+fn channel_sampler(channel: TextureChannel, config: &AmbientCGConfig) -> ImageSampler {
+    match config.channel_samplers.get(&channel) {
+        Some(descriptor) => ImageSampler::Descriptor(descriptor.clone()),
+        None => repeat_sampler(config)
+    }
+}
+
+/// Channel whose sampler a generated image labeled `channel` takes, merged and fallback images count as the map
+/// they stand in for.
+fn sampler_channel(channel: &str) -> Option<TextureChannel> {
+    match channel {
+        "MetallicRoughness" => Some(TextureChannel::Metalness),
+        "FlatNormal" => Some(TextureChannel::NormalGL),
+        _ => TextureChannel::ALL.into_iter().find(|candidate| candidate.suffix() == channel)
+    }
+}
+
+/// Wraps an image synthesized on the CPU, `labeled_image` then gives it the sampler of loaded textures.
+fn generated_image(image: DynamicImage, is_srgb: bool) -> Image {
+    Image::from_dynamic(
+        image,
+        is_srgb,
+        RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD
+    )
+}
+
+/// Gives a generated image the same sampler as textures loaded through the asset server and names it
+/// `{material}#{channel}`, e.g. `Rock023_2K-JPG#MetallicRoughness`, so it can be told apart from other generated
+/// images in inspectors and GPU debuggers. The label is set on both the texture and its sampler, and the image is kept
+/// where `AmbientCGConfig::generated_image_usage` says.
+fn labeled_image(mut image: Image, material_name: &str, channel: &str, config: &AmbientCGConfig, state: &AmbientCGState) -> Image {
+    image.sampler = match sampler_channel(channel) {
+        Some(channel) => channel_sampler(channel, config),
+        None => repeat_sampler(config)
+    };
+    image.asset_usage = config.generated_image_usage;
+    let label = format!("{}#{}", material_name, channel);
+    if let ImageSampler::Descriptor(descriptor) = &mut image.sampler {
+        descriptor.label = Some(label.clone());
+    }
+    let mut labels = state.0.image_labels.lock().unwrap();
+    let interned = match labels.get(label.as_str()) {
+        Some(interned) => *interned,
+        None => {
+            let leaked: &'static str = Box::leak(label.into_boxed_str());
+            labels.insert(leaked);
+            leaked
+        }
+    };
+    image.texture_descriptor.label = Some(interned);
+    image
+}
+
+/// Leaks per channel strings, e.g. `channel_subfolders`, once per distinct value, so registered descriptors can keep
+/// them.
+fn intern_channel_strs(channel_strs: &[(TextureChannel, &str)], state: &AmbientCGState) -> ChannelStrs {
+    if channel_strs.is_empty() {
+        return &[];
+    }
+    let mut interned = state.0.channel_strs.lock().unwrap();
+    if let Some(existing) = interned.iter().find(|existing| **existing == channel_strs) {
+        return existing;
+    }
+    let leaked: ChannelStrs = Box::leak(
+        channel_strs
+            .iter()
+            .map(|(channel, value)| (*channel, &*Box::leak(value.to_string().into_boxed_str())))
+            .collect::<Box<[_]>>()
+    );
+    interned.push(leaked);
+    leaked
+}
+
+#[allow(clippy::too_many_arguments)]
+fn load_merge_inputs(
+    roughness_path: PathBuf,
+    metallic_path: PathBuf,
+    occlusion_path: Option<PathBuf>,
+    occlusion_strength: Option<f32>,
+    roughness_is_gloss: bool,
+    layout: ChannelLayout,
+    config: &AmbientCGConfig,
+    state: &AmbientCGState
+) -> Result<MergeInputs, AmbientCGImportError> {
+    let roughness = load_grayscale_image(&roughness_path, TextureChannel::Roughness, config, state)?;
+    let metallic = load_grayscale_image(&metallic_path, TextureChannel::Metalness, config, state)?;
+    let occlusion_image = match &occlusion_path {
+        Some(path) => Some(load_grayscale_image(path, TextureChannel::AmbientOcclusion, config, state)?),
+        None => None
+    };
+    MergeInputs::new(roughness, metallic, occlusion_image, occlusion_strength, roughness_is_gloss, layout, config)
+}
+
+/// Grayscale roughness, metalness and optionally occlusion maps to pack into a single metallic-roughness image, at
+/// once or a few rows at a time.
+///
+/// The packed image is always 8 bits per channel. Sources are normalized to 8 bits up front, scaling 16-bit maps down
+/// rather than truncating them, so maps of mixed bit depth (e.g. a JPG metalness next to a 16-bit PNG roughness) pack
+/// consistently. 8 bits is plenty for roughness, metalness and occlusion and keeps the texture a quarter of the
+/// size of a 16-bit RGBA one.
+struct MergeInputs {
+    roughness: GrayImage,
+    metallic: GrayImage,
+    occlusion_image: Option<GrayImage>,
+    occlusion_strength: Option<f32>,
+    roughness_is_gloss: bool,
+    progress: Option<fn(u32, u32)>,
+    layout: ChannelLayout
+}
+
+impl MergeInputs {
+    fn new(
+        roughness: DynamicImage,
+        mut metallic: DynamicImage,
+        mut occlusion_image: Option<DynamicImage>,
+        occlusion_strength: Option<f32>,
+        roughness_is_gloss: bool,
+        layout: ChannelLayout,
+        config: &AmbientCGConfig
+    ) -> Result<Self, AmbientCGImportError> {
+        let (width, height) = roughness.dimensions();
+        let inputs = [(TextureChannel::Metalness, Some(&mut metallic)), (TextureChannel::AmbientOcclusion, occlusion_image.as_mut())];
+        for (channel, image) in inputs {
+            let Some(image) = image.filter(|image| image.dimensions() != (width, height)) else {
+                continue;
+            };
+            let error = AmbientCGImportError(AmbientCGErrorType::DimensionMismatch(channel, image.dimensions(), (width, height)));
+            if !config.lenient_decode {
+                return Err(error);
+            }
+            warn!("Resizing AmbientCG texture to merge it: {}", error);
+            *image = image.resize_exact(width, height, image::imageops::FilterType::Triangle);
+        }
+        Ok(Self {
+            roughness: roughness.to_luma8(),
+            metallic: metallic.to_luma8(),
+            occlusion_image: occlusion_image.map(|occlusion| occlusion.to_luma8()),
+            occlusion_strength,
+            roughness_is_gloss,
+            progress: config.merge_progress,
+            layout
+        })
+    }
+
+    fn merge(&self) -> RgbImage {
+        let (width, height) = self.roughness.dimensions();
+        let mut metallic_roughness = RgbImage::new(width, height);
+        self.merge_rows(&mut metallic_roughness, 0..height);
+        metallic_roughness
+    }
+
+    /// Fills `rows` of `metallic_roughness`, which has the dimensions of the inputs.
+    fn merge_rows(&self, metallic_roughness: &mut RgbImage, rows: std::ops::Range<u32>) {
+        let (width, height) = self.roughness.dimensions();
+        for y in rows {
+            for x in 0..width {
+                let mut roughness = self.roughness.get_pixel(x, y)[0];
+                let metallic = self.metallic.get_pixel(x, y)[0];
+
+                if self.roughness_is_gloss {
+                    roughness = 255 - roughness;
+                }
+                let occlusion = self.occlusion_image
+                    .as_ref()
+                    .map_or(0, |occlusion| attenuate_occlusion(occlusion.get_pixel(x, y)[0], self.occlusion_strength));
+
+                // Set the new pixel's color, by default R = occlusion or 0, G = roughness, B = metallic
+                let value = |source: PackedSource| match source {
+                    PackedSource::Occlusion => occlusion,
+                    PackedSource::Roughness => roughness,
+                    PackedSource::Metalness => metallic,
+                    PackedSource::Zero => 0
+                };
+                let color = [value(self.layout.red), value(self.layout.green), value(self.layout.blue)];
+
+                metallic_roughness.get_pixel_mut(x, y).0 = color;
+            }
+            if let Some(progress) = self.progress {
+                progress(y + 1, height);
+            }
+        }
+    }
+}
+
+/// A metallic-roughness merge spread over several frames by `advance_ambient_cg_merges`.
+struct MergeJob {
+    inputs: MergeInputs,
+    output: RgbImage,
+    next_row: u32,
+    rows_per_frame: u32,
+    /// Placeholder image the material already samples, replaced by the merged image once done.
+    handle: Handle<Image>,
+    material_name: String,
+    config: AmbientCGConfig
+}
+
+/// Adds the merged metallic-roughness image, merging it at once or, with `merge_rows_per_frame` set, queuing an
+/// incremental merge behind a neutral placeholder.
+fn add_merged_image(
+    asset_server: &AssetServer,
+    inputs: MergeInputs,
+    material_name: &str,
+    config: &AmbientCGConfig,
+    state: &AmbientCGState
+) -> Handle<Image> {
+    let Some(rows_per_frame) = config.merge_rows_per_frame else {
+        let image = merged_image(inputs.merge(), config);
+        return asset_server.add(labeled_image(image, material_name, "MetallicRoughness", config, state));
+    };
+
+    // unoccluded, fully rough and not metallic until the merge finishes
+    let placeholder = generated_image(DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, image::Rgb([255, 255, 0]))), false);
+    let handle = asset_server.add(labeled_image(placeholder, material_name, "MetallicRoughness", config, state));
+    let (width, height) = inputs.roughness.dimensions();
+    state.0.merges.lock().unwrap().push(MergeJob {
+        inputs,
+        output: RgbImage::new(width, height),
+        next_row: 0,
+        rows_per_frame: rows_per_frame.max(1) as u32,
+        handle: handle.clone(),
+        material_name: material_name.to_string(),
+        config: config.clone()
+    });
+    handle
+}
+
+/// Packs a metalness and a roughness map into the metallic-roughness image `load` generates, laid out by
+/// `AmbientCGConfig::channel_layout`, without adding it to `Assets`, e.g. to assert on the packing or feed a custom
+/// pipeline. Paths are relative to the assets folder unless absolute.
+pub fn pack_metallic_roughness(metallic: &Path, roughness: &Path, config: &AmbientCGConfig) -> Result<Image, AmbientCGImportError> {
+    let inputs = load_merge_inputs(
+        roughness.to_path_buf(),
+        metallic.to_path_buf(),
+        None,
+        None,
+        false,
+        config.channel_layout,
+        config,
+        &AmbientCGState::default()
+    )?;
+    Ok(merged_image(inputs.merge(), config))
+}
+
+/// Wraps a merged metallic-roughness image, with its mip chain when `AmbientCGConfig::merged_mip_chain` is set.
+fn merged_image(merged: RgbImage, config: &AmbientCGConfig) -> Image {
+    if !config.merged_mip_chain {
+        return generated_image(DynamicImage::ImageRgb8(merged), false);
+    }
+    let merged = DynamicImage::ImageRgb8(merged).to_rgba8();
+    let (data, mip_level_count) = mip_chain(&merged);
+    let mut image = generated_image(DynamicImage::ImageRgba8(merged), false);
+    image.data = Some(data);
+    image.texture_descriptor.mip_level_count = mip_level_count;
+    image
+}
+
+/// Every mip level of `image` down to 1x1, laid out one after the other as wgpu expects, and the level count. Each
+/// level is a bilinear downsample of the previous one.
+fn mip_chain(image: &RgbaImage) -> (Vec<u8>, u32) {
+    let mut data = image.as_raw().clone();
+    let mut level = image.clone();
+    let mut mip_level_count = 1;
+    while level.width() > 1 || level.height() > 1 {
+        let (width, height) = ((level.width() / 2).max(1), (level.height() / 2).max(1));
+        level = image::imageops::resize(&level, width, height, image::imageops::FilterType::Triangle);
+        data.extend_from_slice(level.as_raw());
+        mip_level_count += 1;
+    }
+    (data, mip_level_count)
+}
+
+/// Merges the next rows of every queued incremental merge and swaps finished images in for their placeholders.
+fn advance_ambient_cg_merges(mut jobs: Local<Vec<MergeJob>>, mut images: ResMut<Assets<Image>>, state: Res<AmbientCGState>) {
+    jobs.append(&mut state.0.merges.lock().unwrap());
+    jobs.retain_mut(|job| {
+        let height = job.output.height();
+        let end = (job.next_row + job.rows_per_frame).min(height);
+        job.inputs.merge_rows(&mut job.output, job.next_row..end);
+        job.next_row = end;
+        if end < height {
+            return true;
+        }
+        let image = merged_image(std::mem::take(&mut job.output), &job.config);
+        images.insert(&job.handle, labeled_image(image, &job.material_name, "MetallicRoughness", &job.config, &state));
+        false
+    });
+}
+
+/// A 1x1 tangent space normal pointing straight out of the surface.
+fn flat_normal_image() -> Image {
+    generated_image(DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, image::Rgb([128, 128, 255]))), false)
+}
+
+fn create_normal_image(
+    normal_path: PathBuf,
+    channel: TextureChannel,
+    adjustments: NormalAdjustments,
+    config: &AmbientCGConfig
+) -> Result<Image, AmbientCGImportError> {
+    Ok(adjust_normal(load_image(&normal_path, channel, config)?, adjustments))
+}
+
+fn adjust_normal(normal: DynamicImage, adjustments: NormalAdjustments) -> Image {
+    let mut normal = normal.to_rgb8();
+    let x_sign = if adjustments.flip_x { -1.0 } else { 1.0 };
+    let y_sign = if adjustments.flip_y { -1.0 } else { 1.0 };
+
+    for pixel in normal.pixels_mut() {
+        // Unpack to a tangent space vector and scale XY, renormalizing pulls the result toward or away from (0, 0, 1)
+        let x = (pixel[0] as f32 / 255.0 * 2.0 - 1.0) * adjustments.strength * x_sign;
+        let y = (pixel[1] as f32 / 255.0 * 2.0 - 1.0) * adjustments.strength * y_sign;
+        let z = pixel[2] as f32 / 255.0 * 2.0 - 1.0;
+        let direction = Vec3::new(x, y, z).normalize_or_zero();
+
+        pixel.0 = direction.to_array().map(|c| ((c * 0.5 + 0.5) * 255.0).round() as u8);
+    }
+
+    generated_image(DynamicImage::ImageRgb8(normal), false)
+}
+
+fn create_occlusion_image(
+    occlusion_path: PathBuf,
+    strength: f32,
+    config: &AmbientCGConfig,
+    state: &AmbientCGState
+) -> Result<Image, AmbientCGImportError> {
+    Ok(attenuate_occlusion_image(load_grayscale_image(&occlusion_path, TextureChannel::AmbientOcclusion, config, state)?, Some(strength)))
+}
+
+fn attenuate_occlusion_image(occlusion: DynamicImage, strength: Option<f32>) -> Image {
+    let mut occlusion = occlusion.to_luma8();
+
+    for pixel in occlusion.pixels_mut() {
+        pixel.0 = [attenuate_occlusion(pixel[0], strength)];
+    }
+
+    generated_image(DynamicImage::ImageLuma8(occlusion), false)
+}
+
+/// Samples a height map at `uv` with bilinear filtering, wrapping like the repeat sampler. Returns 0.0 to 1.0.
+fn sample_height(height: &ImageBuffer<Luma<u16>, Vec<u16>>, uv: Vec2) -> f32 {
+    let (width, height_px) = height.dimensions();
+    let size = Vec2::new(width as f32, height_px as f32);
+    // texel centers sit at half integers
+    let texel = uv.rem_euclid(Vec2::ONE) * size - 0.5;
+    let base = texel.floor();
+    let t = texel - base;
+    let fetch = |dx: f32, dy: f32| {
+        let p = (base + Vec2::new(dx, dy)).rem_euclid(size);
+        let pixel = height.get_pixel((p.x as u32).min(width - 1), (p.y as u32).min(height_px - 1));
+        pixel[0] as f32 / u16::MAX as f32
+    };
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    lerp(lerp(fetch(0.0, 0.0), fetch(1.0, 0.0), t.x), lerp(fetch(0.0, 1.0), fetch(1.0, 1.0), t.x), t.y)
+}
+
+/// Blends an occlusion value toward white (unoccluded) by `1.0 - strength`.
+fn attenuate_occlusion(occlusion: u8, strength: Option<f32>) -> u8 {
+    let Some(strength) = strength else {
+        return occlusion;
+    };
+    let occluded = (255 - occlusion) as f32 * strength;
+    255 - occluded.round().clamp(0.0, 255.0) as u8
+}
+
+/// Unwraps the result of a `try_` load for the panicking variants, which report the error as their panic message.
+fn expect_loaded<T>(result: Result<T, AmbientCGImportError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => panic!("{}", err)
+    }
+}
+
+/// Turns a failed decode into a missing map with a warning under `AmbientCGConfig::lenient_decode`.
+fn lenient<T>(result: Result<T, AmbientCGImportError>, config: &AmbientCGConfig) -> Result<Option<T>, AmbientCGImportError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if config.lenient_decode => {
+            warn!("Skipping AmbientCG texture: {}", error);
+            Ok(None)
+        }
+        Err(error) => Err(error)
+    }
+}
+
+/// Decodes a map as grayscale, reusing an earlier decode of the same file in `state` if it hasn't been modified since.
+fn load_grayscale_image(
+    path: &Path,
+    channel: TextureChannel,
+    config: &AmbientCGConfig,
+    state: &AmbientCGState
+) -> Result<DynamicImage, AmbientCGImportError> {
+    #[cfg(feature = "embedded")]
+    if config.embedded_textures.contains_key(path) {
+        return Ok(to_grayscale(load_image(path, channel, config)?, channel, config));
+    }
+    let Some(modified) = config.file_system.modified(&config.file_path(path)) else {
+        return Ok(to_grayscale(load_image(path, channel, config)?, channel, config));
+    };
+    // the full size decode is cached so a changed `max_texture_size` applies to cached maps too
+    let source = grayscale_source(channel, config);
+    if let Some(image) = state.0.grayscale_cache.lock().unwrap().get(path, source, modified) {
+        return Ok(fit_texture_size(image, config));
+    }
+    let image = to_grayscale(decode_image(path, channel, config)?, channel, config);
+    state.0.grayscale_cache.lock().unwrap().insert(path.to_path_buf(), source, modified, image.clone());
+    Ok(fit_texture_size(image, config))
+}
+
+fn grayscale_source(channel: TextureChannel, config: &AmbientCGConfig) -> GrayscaleSource {
+    config.grayscale_sources.get(&channel).copied().unwrap_or_default()
+}
+
+/// Reduces a decoded map of `channel` to its configured grayscale source. Maps decoded as 8-bit grayscale are
+/// returned as they are, whatever the source, since every color channel of them holds the same value.
+fn to_grayscale(image: DynamicImage, channel: TextureChannel, config: &AmbientCGConfig) -> DynamicImage {
+    let index = match (&image, grayscale_source(channel, config)) {
+        (DynamicImage::ImageLuma8(_), _) => return image,
+        (_, GrayscaleSource::Luma) => return image.grayscale(),
+        (_, GrayscaleSource::Red) => 0,
+        (_, GrayscaleSource::Green) => 1,
+        (_, GrayscaleSource::Blue) => 2
+    };
+    let rgb = image.to_rgb8();
+    DynamicImage::ImageLuma8(GrayImage::from_fn(rgb.width(), rgb.height(), |x, y| Luma([rgb.get_pixel(x, y)[index]])))
+}
+
+/// Decodes the alpha channel of a map as grayscale, `None` with a warning if the map has no alpha.
+fn load_alpha_image(path: &Path, channel: TextureChannel, config: &AmbientCGConfig) -> Result<Option<DynamicImage>, AmbientCGImportError> {
+    let alpha = alpha_channel(&load_image(path, channel, config)?);
+    if alpha.is_none() {
+        warn!("{} has no alpha channel to read metalness from", path.display());
+    }
+    Ok(alpha.map(DynamicImage::ImageLuma8))
+}
+
+fn alpha_channel(image: &DynamicImage) -> Option<GrayImage> {
+    if !image.color().has_alpha() {
+        return None;
+    }
+    let rgba = image.to_rgba8();
+    Some(GrayImage::from_fn(rgba.width(), rgba.height(), |x, y| Luma([rgba.get_pixel(x, y)[3]])))
+}
+
+/// Least recently used grayscale decodes, keyed by path and grayscale source, and by modification time so edited
+/// files are decoded again.
+#[derive(Default)]
+struct GrayscaleCache {
+    entries: std::collections::VecDeque<(PathBuf, GrayscaleSource, std::time::SystemTime, DynamicImage)>,
+    bytes: usize
+}
+
+impl GrayscaleCache {
+    /// Upper bound on the decoded bytes kept, a 4K map is 16 MiB as 8-bit grayscale.
+    const MAX_BYTES: usize = 64 * 1024 * 1024;
+
+    fn get(&mut self, path: &Path, source: GrayscaleSource, modified: std::time::SystemTime) -> Option<DynamicImage> {
+        let index = self.entries.iter().position(|(cached, cached_source, cached_modified, _)| {
+            cached == path && *cached_source == source && *cached_modified == modified
+        })?;
+        let entry = self.entries.remove(index)?;
+        let image = entry.3.clone();
+        self.entries.push_back(entry);
+        Some(image)
+    }
+
+    fn insert(&mut self, path: PathBuf, source: GrayscaleSource, modified: std::time::SystemTime, image: DynamicImage) {
+        let size = image.as_bytes().len();
+        if size > Self::MAX_BYTES {
+            return;
+        }
+        // an older decode of the same file is stale now
+        self.entries.retain(|(cached, cached_source, _, cached_image)| {
+            let stale = *cached == path && *cached_source == source;
+            if stale {
+                self.bytes -= cached_image.as_bytes().len();
+            }
+            !stale
+        });
+        while self.bytes + size > Self::MAX_BYTES {
+            let Some((_, _, _, evicted)) = self.entries.pop_front() else {
+                break;
+            };
+            self.bytes -= evicted.as_bytes().len();
+        }
+        self.bytes += size;
+        self.entries.push_back((path, source, modified, image));
+    }
+}
+
+/// Decodes a map, downsampled to `AmbientCGConfig::max_texture_size`.
+fn load_image(path: &Path, channel: TextureChannel, config: &AmbientCGConfig) -> Result<DynamicImage, AmbientCGImportError> {
+    Ok(fit_texture_size(decode_image(path, channel, config)?, config))
+}
+
+/// Whether the map at `path` exceeds `AmbientCGConfig::max_texture_size`, in which case it is downsampled on the CPU
+/// instead of handed to the asset server. Only the image header is read.
+fn is_oversized(path: &Path, config: &AmbientCGConfig) -> bool {
+    config.max_texture_size.is_some_and(|max| read_dimensions(path, config).is_some_and(|(width, height)| width.max(height) > max))
+}
+
+/// Shrinks `image` to fit `AmbientCGConfig::max_texture_size`, smaller images are returned as they are.
+fn fit_texture_size(image: DynamicImage, config: &AmbientCGConfig) -> DynamicImage {
+    match config.max_texture_size {
+        Some(max) if image.width().max(image.height()) > max => image.resize(max, max, image::imageops::FilterType::Triangle),
+        _ => image
+    }
+}
+
+fn decode_image(path: &Path, channel: TextureChannel, config: &AmbientCGConfig) -> Result<DynamicImage, AmbientCGImportError> {
+    #[cfg(feature = "embedded")]
+    if let Some(bytes) = config.embedded_textures.get(path).copied() {
+        return image::load_from_memory(bytes)
+            .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Decode(channel, path.to_path_buf(), error)));
+    }
+    let absolute_path = resolve_resource_path(path, config.asset_root.as_deref());
+    let read_error = |error| AmbientCGImportError(AmbientCGErrorType::Decode(channel, absolute_path.clone(), image::ImageError::IoError(error)));
+    ImageReader::new(Cursor::new(config.file_system.read(&config.file_path(path)).map_err(read_error)?))
+        .with_guessed_format()
+        .map_err(read_error)?
+        .decode()
+        .map_err(|error| AmbientCGImportError(AmbientCGErrorType::Decode(channel, absolute_path, error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::image::ImageFilterMode;
+
+    #[test]
+    fn channel_paths_bypass_naming_convention() {
+        let material = AmbientCGMaterial {
+            name: "Bricks075",
+            channel_paths: &[(TextureChannel::Roughness, "packs/odd/rough_final.png")],
+            ..AmbientCGMaterial::DEFAULT
+        };
+        let paths = material.texture_paths(&AmbientCGConfig::default());
+
+        assert_eq!(paths.roughness, Path::new("packs/odd/rough_final.png"));
+        assert_eq!(paths.color, Path::new("materials/Bricks075_1K-JPG/Bricks075_1K-JPG_Color.jpg"));
+        assert_eq!(material.detached(&AmbientCGState::default()).channel_paths, material.channel_paths);
+    }
+
+    #[test]
+    fn fit_texture_size_keeps_aspect_ratio() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(8, 4));
+        let config = AmbientCGConfig { max_texture_size: Some(4), ..default() };
+        assert_eq!(fit_texture_size(image.clone(), &config).dimensions(), (4, 2));
+        assert_eq!(fit_texture_size(image.clone(), &AmbientCGConfig { max_texture_size: Some(8), ..default() }).dimensions(), (8, 4));
+        assert_eq!(fit_texture_size(image, &AmbientCGConfig::default()).dimensions(), (8, 4));
+    }
+
+    #[test]
+    fn grayscale_reads_configured_source_channel() {
+        let packed = DynamicImage::ImageRgb8(RgbImage::from_pixel(2, 1, image::Rgb([30, 140, 250])));
+        let config = AmbientCGConfig {
+            grayscale_sources: HashMap::from([(TextureChannel::Roughness, GrayscaleSource::Green)]),
+            ..default()
+        };
+        assert_eq!(to_grayscale(packed.clone(), TextureChannel::Roughness, &config).to_luma8().get_pixel(1, 0), &Luma([140]));
+        assert_eq!(to_grayscale(packed.clone(), TextureChannel::Metalness, &config), packed.grayscale());
+        // an already grayscale decode is kept rather than converted again
+        let gray = DynamicImage::ImageLuma8(GrayImage::from_pixel(2, 1, Luma([60])));
+        assert_eq!(to_grayscale(gray.clone(), TextureChannel::Roughness, &config), gray);
+    }
+
+    #[test]
+    fn mip_chain_halves_down_to_one_pixel() {
+        let image = RgbaImage::from_pixel(4, 2, image::Rgba([10, 120, 240, 255]));
+        let (data, mip_level_count) = mip_chain(&image);
+        // 4x2, 2x1 and 1x1
+        assert_eq!(mip_level_count, 3);
+        assert_eq!(data.len(), (8 + 2 + 1) * 4);
+        assert_eq!(&data[data.len() - 4..], &[10, 120, 240, 255]);
+    }
+
+    #[test]
+    fn roughness_from_color_stands_in_for_missing_roughness() {
+        let color = "materials/Virtual001_1K-JPG/Virtual001_1K-JPG_Color.jpg";
+        let config = AmbientCGConfig {
+            file_system: Arc::new(InMemoryFileSystem::new().with_file(color, Vec::new())),
+            ..default()
+        };
+        let material = AmbientCGMaterial { name: "Virtual001", ..AmbientCGMaterial::DEFAULT };
+        assert_eq!(material.describe(&config).unwrap().metallic_roughness_texture, None);
+
+        let material = AmbientCGMaterial { roughness_from_color: true, ..material };
+        assert_eq!(material.describe(&config).unwrap().metallic_roughness_texture, Some(TextureSource::Generated {
+            label: "Virtual001_1K-JPG#MetallicRoughness".to_string(),
+            sources: vec![PathBuf::from(color)]
+        }));
+    }
+
+    #[test]
+    fn subfolder_naming_the_material_folder_is_not_doubled() {
+        let config = AmbientCGConfig::default();
+        let material = |subfolder| AmbientCGMaterial { name: "Rock023", resolution: AmbientCGResolution::TwoK, subfolder, ..AmbientCGMaterial::DEFAULT };
+
+        let doubled = material(Some("Rock023_2K-JPG")).texture_paths(&config);
+        assert_eq!(doubled.folder, Path::new("materials/Rock023_2K-JPG"));
+        assert_eq!(material(Some("rocks/Rock023_2K-JPG")).texture_paths(&config), material(Some("rocks")).texture_paths(&config));
+        // another material's folder is a plain subfolder
+        assert_eq!(material(Some("Rock024_2K-JPG")).texture_paths(&config).folder, Path::new("materials/Rock024_2K-JPG/Rock023_2K-JPG"));
+    }
+
+    #[test]
+    fn shared_resolution_is_highest_common_to_every_member() {
+        let folders = ["Wall_1K-JPG", "Wall_2K-JPG", "Wall_4K-JPG", "Floor_1K-JPG", "Floor_2K-JPG", "Trim_2K-JPG", "Trim_8K-JPG"];
+        let file_system = folders.iter().fold(InMemoryFileSystem::new(), |file_system, folder| {
+            file_system.with_file(format!("materials/{0}/{0}_Color.jpg", folder), Vec::new())
+        });
+        let config = AmbientCGConfig { file_system: Arc::new(file_system), ..default() };
+        let member = |name| AmbientCGMaterial { name, resolution: AmbientCGResolution::EightK, ..AmbientCGMaterial::DEFAULT };
+
+        let set = [member("Wall"), member("Floor"), member("Trim")];
+        assert_eq!(AmbientCGMaterial::shared_resolution(&set, &config).unwrap(), AmbientCGResolution::TwoK);
+        let capped = [member("Wall"), AmbientCGMaterial { resolution: AmbientCGResolution::OneK, ..member("Floor") }];
+        assert_eq!(AmbientCGMaterial::shared_resolution(&capped, &config).unwrap(), AmbientCGResolution::OneK);
+        assert!(AmbientCGMaterial::shared_resolution(&[member("Wall"), AmbientCGMaterial { resolution: AmbientCGResolution::OneK, ..member("Trim") }], &config).is_err());
+    }
+
+    #[test]
+    fn alpha_channel_reads_packed_metalness() {
+        let packed = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(2, 1, image::Rgba([90, 60, 30, 200])));
+        assert_eq!(alpha_channel(&packed).unwrap().get_pixel(1, 0), &Luma([200]));
+        // without alpha every pixel would read as fully metallic, so it is no source at all
+        assert!(alpha_channel(&DynamicImage::ImageRgb8(RgbImage::new(2, 1))).is_none());
+    }
+
+    #[test]
+    fn resolution_negotiator_can_be_replaced() {
+        #[derive(Debug)]
+        struct Smallest;
+        impl ResolutionNegotiator for Smallest {
+            fn negotiate(&self, material: &AmbientCGMaterial<'_>, config: &AmbientCGConfig) -> Result<AmbientCGResolution, AmbientCGImportError> {
+                material.available_resolutions(config).last().copied().ok_or_else(|| material.missing_files(config))
+            }
+        }
+        let file_system = Arc::new(InMemoryFileSystem::new()
+            .with_file("materials/Virtual001_1K-JPG/Virtual001_1K-JPG_Color.jpg", Vec::new())
+            .with_file("materials/Virtual001_2K-JPG/Virtual001_2K-JPG_Color.jpg", Vec::new()));
+        let material = AmbientCGMaterial { name: "Virtual001", resolution: AmbientCGResolution::FourK, ..AmbientCGMaterial::DEFAULT };
+
+        let config = AmbientCGConfig { file_system, ..default() };
+        assert_eq!(material.negotiated(&config).unwrap().resolution, AmbientCGResolution::TwoK);
+        let config = AmbientCGConfig { resolution_negotiator: Arc::new(Smallest), ..config };
+        assert_eq!(material.negotiated(&config).unwrap().resolution, AmbientCGResolution::OneK);
+    }
+
+    #[test]
+    fn enabled_image_features_cover_every_variant() {
+        // the crate enables bevy's jpeg feature and keeps its default png one
+        assert_eq!(missing_image_features(), Vec::new());
+    }
+
+    #[test]
+    fn mesh_uv_scale_repeats_across_largest_dimension() {
+        let wall = Mesh::from(Cuboid::new(10.0, 0.2, 4.0));
+        let uv_scale = mesh_uv_scale(&wall, 5.0).unwrap();
+        assert!((uv_scale - Vec2::new(5.0, 2.0)).length() < 1e-5, "unexpected scale {}", uv_scale);
+        assert_eq!(mesh_uv_scale(&Mesh::new(bevy::render::mesh::PrimitiveTopology::TriangleList, RenderAssetUsages::default()), 5.0), None);
+        for repeats in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(mesh_uv_scale(&wall, repeats), None);
+        }
+    }
+
+    #[test]
+    fn in_memory_file_system_serves_virtual_material_tree() {
+        let mut png = Vec::new();
+        DynamicImage::ImageLuma8(GrayImage::from_pixel(2, 2, Luma([77])))
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let config = AmbientCGConfig {
+            file_system: Arc::new(InMemoryFileSystem::new().with_file("materials/Virtual001_2K-JPG/Virtual001_2K-JPG_Roughness.png", png)),
+            ..default()
+        };
+        let material = AmbientCGMaterial { name: "Virtual001", resolution: AmbientCGResolution::FourK, ..AmbientCGMaterial::DEFAULT };
+
+        let material = material.negotiated(&config).unwrap();
+        assert_eq!(material.resolution, AmbientCGResolution::TwoK);
+        let path = find_texture(&material.texture_paths(&config).roughness, &config).unwrap();
+        let roughness = load_grayscale_image(&path, TextureChannel::Roughness, &config, &AmbientCGState::default()).unwrap();
+        assert_eq!(roughness.to_luma8().get_pixel(1, 1), &Luma([77]));
+        assert!(find_texture(&material.texture_paths(&config).color, &config).is_none());
+    }
+
+    #[cfg(feature = "hdri")]
+    #[test]
+    fn environment_resolution_is_negotiated_among_files_on_disk() {
+        let config = AmbientCGConfig {
+            file_system: Arc::new(InMemoryFileSystem::new()
+                .with_file("materials/Sky001_1K-HDR.exr", Vec::new())
+                .with_file("materials/Sky001_8K-HDR.hdr", Vec::new())),
+            ..default()
+        };
+        let sky = AmbientCGEnvironment { name: "Sky001", resolution: AmbientCGResolution::FourK, subfolder: None };
+
+        assert_eq!(sky.find(&config).unwrap(), Path::new("materials/Sky001_1K-HDR.exr"));
+        let config = AmbientCGConfig { resolution_policy: AmbientCGResolutionPolicy::Exact, ..config };
+        assert!(sky.find(&config).is_err());
+    }
+
+    #[test]
+    fn discover_and_audit_go_through_the_file_system() {
+        let file_system = InMemoryFileSystem::new()
+            .with_file("materials/Virtual001_1K-JPG/Virtual001_1K-JPG_Color.jpg", Vec::new())
+            .with_file("materials/rocks/Virtual002_2K-PNG/Virtual002_2K-PNG_NormalGL.png", Vec::new())
+            // a file named like a material folder is no material
+            .with_file("materials/Virtual003_1K-JPG", Vec::new());
+        let config = AmbientCGConfig { file_system: Arc::new(file_system), ..default() };
+
+        assert_eq!(config.discover(), vec![
+            ("Virtual001".to_string(), AmbientCGResolution::OneK, AmbientCGVariant::Jpg),
+            ("Virtual002".to_string(), AmbientCGResolution::TwoK, AmbientCGVariant::Png)
+        ]);
+        let audits = config.audit();
+        assert_eq!(audits[0].channels, vec![TextureChannel::Color]);
+        assert_eq!(audits[1].subfolder, Path::new("rocks"));
+        assert_eq!(audits[1].variant, AmbientCGVariant::Png);
+        assert_eq!(audits[1].channels, vec![TextureChannel::NormalGL]);
+    }
+
+    #[test]
+    fn mixed_resolution_folder_reports_mismatched_map() {
+        let materials = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/materials");
+        let config = AmbientCGConfig { materials_path: materials.into(), ..default() };
+        let material = |name| AmbientCGMaterial { name, ..AmbientCGMaterial::DEFAULT };
+
+        // Test005 pairs an 8x8 color map with a 4x4 roughness map in its 1K folder
+        let (channel, path) = mixed_resolution_channel(&material("Test005").texture_paths(&config), &config).unwrap();
+        assert_eq!(channel, TextureChannel::Roughness);
+        assert!(path.ends_with("Test005_1K-JPG/Test005_1K-JPG_Roughness.jpg"));
+        assert_eq!(mixed_resolution_channel(&material("Test001").texture_paths(&config), &config), None);
+    }
+
+    #[test]
+    fn world_scale_repeats_once_per_texture_size() {
+        assert_eq!(world_uv_scale(Vec2::new(200.0, 50.0), 2.0), Vec2::new(100.0, 25.0));
+        assert_eq!(world_uv_scale(Vec2::splat(3.0), 1.0), Vec2::splat(3.0));
+    }
+
+    #[test]
+    fn reflectance_follows_ior() {
+        // Bevy's default reflectance of 0.5 is an IOR of 1.5
+        assert!((AmbientCGMaterial::reflectance_from_ior(1.5) - 0.5).abs() < 1e-6);
+        assert_eq!(AmbientCGMaterial::reflectance_from_ior(1.0), 0.0);
+
+        let water = AmbientCGMaterial { reflectance: Some(AmbientCGMaterial::reflectance_from_ior(1.33)), ..AmbientCGMaterial::DEFAULT };
+        let material = water.standard_material(MaterialTextures::default(), Vec2::ZERO);
+        assert_eq!(material.reflectance, AmbientCGMaterial::reflectance_from_ior(1.33));
+        assert_eq!(AmbientCGMaterial::DEFAULT.standard_material(MaterialTextures::default(), Vec2::ZERO).reflectance, 0.5);
+    }
+
+    #[test]
+    fn lenient_decode_turns_failures_into_missing_maps() {
+        let failure = || Err::<(), _>(AmbientCGImportError(AmbientCGErrorType::DecodeBytes(
+            TextureChannel::Roughness,
+            image::ImageError::Unsupported(image::error::UnsupportedError::from(image::error::ImageFormatHint::Unknown))
+        )));
+        let lenient_config = AmbientCGConfig { lenient_decode: true, ..default() };
+
+        assert!(matches!(lenient(failure(), &lenient_config), Ok(None)));
+        assert!(lenient(failure(), &AmbientCGConfig::default()).is_err());
+        assert!(matches!(lenient(Ok(1), &AmbientCGConfig::default()), Ok(Some(1))));
+    }
+
+    #[test]
+    fn with_name_uses_default_settings() {
+        let config = AmbientCGConfig { default_resolution: AmbientCGResolution::TwoK, ..default() };
+        let material = AmbientCGMaterial::with_name("Rock023", &config);
+        assert_eq!(material.name, "Rock023");
+        assert_eq!(material.resolution, AmbientCGResolution::TwoK);
+        assert_eq!(AmbientCGMaterial { name: "", resolution: AmbientCGResolution::OneK, ..material }, AmbientCGMaterial::DEFAULT);
+    }
+
+    #[test]
+    fn generated_images_follow_configured_asset_usage() {
+        let config = AmbientCGConfig {
+            generated_image_usage: RenderAssetUsages::RENDER_WORLD,
+            ..default()
+        };
+        let image = labeled_image(flat_normal_image(), "Rock023_1K-JPG", "FlatNormal", &config, &AmbientCGState::default());
+        assert_eq!(image.asset_usage, RenderAssetUsages::RENDER_WORLD);
+
+        let image = labeled_image(flat_normal_image(), "Rock023_1K-JPG", "FlatNormal", &AmbientCGConfig::default(), &AmbientCGState::default());
+        assert!(image.asset_usage.contains(RenderAssetUsages::MAIN_WORLD));
+    }
+
+    #[test]
+    fn channel_subfolders_override_single_channels() {
+        let material = AmbientCGMaterial {
+            name: "Bricks075",
+            subfolder: Some("bricks/red"),
+            channel_subfolders: &[(TextureChannel::NormalGL, "bricks/shared")],
+            ..AmbientCGMaterial::DEFAULT
+        };
+        let paths = material.texture_paths(&AmbientCGConfig::default());
+
+        assert_eq!(paths.color, Path::new("materials/bricks/red/Bricks075_1K-JPG/Bricks075_1K-JPG_Color.jpg"));
+        assert_eq!(paths.normal_gl, Path::new("materials/bricks/shared/Bricks075_1K-JPG/Bricks075_1K-JPG_NormalGL.jpg"));
+        assert_eq!(material.detached(&AmbientCGState::default()).channel_subfolders, material.channel_subfolders);
+    }
+
+    #[test]
+    fn roughness_and_metallic_factors_scale_their_maps() {
+        let textures = MaterialTextures { has_metalness: true, has_roughness: true, ..default() };
+        let rougher = AmbientCGMaterial { perceptual_roughness: Some(1.2), metallic: Some(0.5), ..AmbientCGMaterial::DEFAULT };
+
+        let material = rougher.standard_material(textures.clone(), Vec2::ZERO);
+        assert_eq!((material.perceptual_roughness, material.metallic), (1.2, 0.5));
+        let material = AmbientCGMaterial::DEFAULT.standard_material(textures, Vec2::ZERO);
+        assert_eq!((material.perceptual_roughness, material.metallic), (1.0, 1.0));
+    }
+
+    #[test]
+    fn standard_material_passthroughs_override_defaults() {
+        let decal = AmbientCGMaterial { cull_mode: Some(Some(Face::Front)), depth_bias: Some(2.0), fog_enabled: Some(false), ..AmbientCGMaterial::DEFAULT };
+        let material = decal.standard_material(MaterialTextures::default(), Vec2::ZERO);
+        assert_eq!((material.cull_mode, material.depth_bias, material.fog_enabled), (Some(Face::Front), 2.0, false));
+
+        let defaults = StandardMaterial::default();
+        let material = AmbientCGMaterial::DEFAULT.standard_material(MaterialTextures::default(), Vec2::ZERO);
+        assert_eq!((material.cull_mode, material.depth_bias, material.fog_enabled), (defaults.cull_mode, defaults.depth_bias, defaults.fog_enabled));
+        assert_ne!(decal, AmbientCGMaterial::DEFAULT);
+    }
+
+    #[test]
+    fn base_color_tints_the_material() {
+        let mossy = AmbientCGMaterial { base_color: Some(Color::srgb(0.6, 0.8, 0.5)), ..AmbientCGMaterial::DEFAULT };
+        let dry = AmbientCGMaterial { base_color: Some(Color::srgb(0.9, 0.8, 0.6)), ..AmbientCGMaterial::DEFAULT };
+
+        assert_eq!(mossy.standard_material(MaterialTextures::default(), Vec2::ZERO).base_color, Color::srgb(0.6, 0.8, 0.5));
+        assert_eq!(AmbientCGMaterial::DEFAULT.standard_material(MaterialTextures::default(), Vec2::ZERO).base_color, Color::WHITE);
+        assert_ne!(mossy, dry);
+        assert_ne!(mossy, AmbientCGMaterial::DEFAULT);
+    }
+
+    #[test]
+    fn decals_clamp_instead_of_tiling() {
+        let config = AmbientCGConfig::default();
+        let decal = AmbientCGMaterial { tiling: false, ..AmbientCGMaterial::DEFAULT };
+        let address_mode = |material: &AmbientCGMaterial| match material.loader_settings(TextureChannel::Color, &config).sampler {
+            ImageSampler::Descriptor(descriptor) => (descriptor.address_mode_u, descriptor.address_mode_v),
+            ImageSampler::Default => panic!("expected a sampler descriptor")
+        };
+        // ImageAddressMode has no PartialEq
+        assert!(matches!(address_mode(&AmbientCGMaterial::DEFAULT), (ImageAddressMode::Repeat, ImageAddressMode::Repeat)));
+        assert!(matches!(address_mode(&decal), (ImageAddressMode::ClampToEdge, ImageAddressMode::ClampToEdge)));
+        assert_ne!(decal, AmbientCGMaterial::DEFAULT);
+    }
+
+    #[test]
+    fn from_px_maps_to_nearest_resolution() {
+        assert_eq!(AmbientCGResolution::from_px(0), AmbientCGResolution::OneK);
+        assert_eq!(AmbientCGResolution::from_px(1535), AmbientCGResolution::OneK);
+        assert_eq!(AmbientCGResolution::from_px(1536), AmbientCGResolution::TwoK);
+        assert_eq!(AmbientCGResolution::from_px(4096), AmbientCGResolution::FourK);
+        assert_eq!(AmbientCGResolution::from_px(14335), AmbientCGResolution::TwelveK);
+        assert_eq!(AmbientCGResolution::from_px(14336), AmbientCGResolution::SixteenK);
+        assert_eq!(AmbientCGResolution::from_px(u32::MAX), AmbientCGResolution::SixteenK);
+        for resolution in [AmbientCGResolution::OneK, AmbientCGResolution::TwelveK, AmbientCGResolution::SixteenK] {
+            assert_eq!(AmbientCGResolution::from_px(resolution.width_px()), resolution);
+        }
+    }
+
+    #[test]
+    fn grayscale_cache_evicts_least_recently_used_and_stale_decodes() {
+        let image = |side| DynamicImage::ImageLuma8(ImageBuffer::from_pixel(side, side, Luma([0u8])));
+        let now = std::time::SystemTime::now();
+        let later = now + std::time::Duration::from_secs(1);
+        // each image is half the budget, so only two fit
+        let side = ((GrayscaleCache::MAX_BYTES / 2) as f64).sqrt() as u32;
+        let mut cache = GrayscaleCache::default();
+
+        cache.insert("a".into(), GrayscaleSource::Luma, now, image(side));
+        cache.insert("b".into(), GrayscaleSource::Luma, now, image(side));
+        assert!(cache.get(Path::new("a"), GrayscaleSource::Luma, now).is_some());
+        cache.insert("c".into(), GrayscaleSource::Luma, now, image(side));
+        assert!(cache.get(Path::new("b"), GrayscaleSource::Luma, now).is_none(), "least recently used entry is evicted");
+        assert!(cache.get(Path::new("a"), GrayscaleSource::Luma, now).is_some());
+
+        assert!(cache.get(Path::new("a"), GrayscaleSource::Luma, later).is_none(), "a modified file is not served from cache");
+        cache.insert("a".into(), GrayscaleSource::Luma, later, image(1));
+        assert!(cache.get(Path::new("a"), GrayscaleSource::Luma, now).is_none());
+        assert_eq!(cache.entries.len(), 2);
+    }
+
+    #[test]
+    fn merge_scales_mixed_bit_depths_to_eight_bits() {
+        // 16-bit roughness next to 8-bit metalness, 0x8080 is exactly 128 once scaled down
+        let roughness = DynamicImage::ImageLuma16(ImageBuffer::from_pixel(2, 2, Luma([0x8080u16])));
+        let metallic = DynamicImage::ImageLuma8(ImageBuffer::from_pixel(2, 2, Luma([50u8])));
+        let merged = MergeInputs::new(roughness, metallic, None, None, false, ChannelLayout::GLTF, &AmbientCGConfig::default()).unwrap().merge();
+        assert_eq!(merged.get_pixel(1, 1).0, [0, 128, 50]);
+    }
+
+    #[test]
+    fn merge_keeps_non_square_inputs_in_place() {
+        // a 2048x512 trim sheet in miniature, each pixel encodes its own position so a transposed merge shows up
+        let ramp = |offset: u32| DynamicImage::ImageLuma8(ImageBuffer::from_fn(8, 2, |x, y| Luma([(offset + x + y * 8) as u8])));
+        let merged = MergeInputs::new(ramp(0), ramp(100), Some(ramp(200)), None, false, ChannelLayout::GLTF, &AmbientCGConfig::default()).unwrap().merge();
+        assert_eq!(merged.dimensions(), (8, 2));
+        assert_eq!(merged.get_pixel(7, 0).0, [207, 7, 107]);
+        assert_eq!(merged.get_pixel(2, 1).0, [210, 10, 110]);
+    }
+
+    #[test]
+    fn uv_scale_keeps_axes_independent() {
+        let material = AmbientCGMaterial::DEFAULT.configured_material(MaterialTextures::default(), Vec2::new(4.0, 1.0), &default());
+        assert_eq!(material.uv_transform.transform_point2(Vec2::ONE), Vec2::new(4.0, 1.0));
+    }
+
+    #[test]
+    fn merge_of_mismatched_dimensions_is_an_error() {
+        let gray = |width| DynamicImage::ImageLuma8(ImageBuffer::from_pixel(width, 2, Luma([100])));
+        let error = MergeInputs::new(gray(4), gray(2), None, None, false, ChannelLayout::GLTF, &AmbientCGConfig::default()).err().unwrap();
+        assert!(matches!(error.0, AmbientCGErrorType::DimensionMismatch(TextureChannel::Metalness, (2, 2), (4, 2))));
+        assert_eq!(error.to_string(), "Texture dimensions differ from the roughness map it merges with: Metalness is 2x2, roughness is 4x2");
+        let error = MergeInputs::new(gray(4), gray(4), Some(gray(8)), None, false, ChannelLayout::GLTF, &AmbientCGConfig::default()).err().unwrap();
+        assert!(matches!(error.0, AmbientCGErrorType::DimensionMismatch(TextureChannel::AmbientOcclusion, (8, 2), (4, 2))));
+
+        let lenient = AmbientCGConfig { lenient_decode: true, ..default() };
+        let merged = MergeInputs::new(gray(4), gray(2), Some(gray(8)), None, false, ChannelLayout::GLTF, &lenient).unwrap().merge();
+        assert_eq!(merged.dimensions(), (4, 2));
+    }
+
+    #[test]
+    fn incremental_merge_matches_synchronous_merge() {
+        let gray = |value| DynamicImage::ImageLuma8(ImageBuffer::from_pixel(3, 5, Luma([value])));
+        let inputs = MergeInputs::new(gray(200), gray(50), Some(gray(180)), None, false, ChannelLayout::GLTF, &AmbientCGConfig::default()).unwrap();
+        let mut incremental = RgbImage::new(3, 5);
+        for start in (0..5).step_by(2) {
+            inputs.merge_rows(&mut incremental, start..(start + 2).min(5));
+        }
+        assert_eq!(incremental, inputs.merge());
+    }
+
+    #[test]
+    fn only_color_is_loaded_as_srgb() {
+        let config = AmbientCGConfig::default();
+        for channel in TextureChannel::ALL {
+            let settings = AmbientCGMaterial::DEFAULT.loader_settings(channel, &config);
+            assert_eq!(settings.is_srgb, channel == TextureChannel::Color, "unexpected color space for {:?}", channel);
+        }
+
+        let linear = AmbientCGMaterial {
+            linear_color: true,
+            ..AmbientCGMaterial::DEFAULT
+        };
+        assert!(!linear.loader_settings(TextureChannel::Color, &config).is_srgb);
+    }
+
+    #[test]
+    fn errors_display_readable_messages() {
+        let error = AmbientCGMaterial::from_asset_id("Rock 023", &AmbientCGConfig::default()).unwrap_err();
+        assert_eq!(error.to_string(), "Asset ID must look like Rock023 or Rock023_2K-JPG, got \"Rock 023\"");
+
+        let error = AmbientCGImportError(AmbientCGErrorType::Io(
+            PathBuf::from("materials/Rock023_1K-JPG/Rock023_1K-JPG_Color.jpg"),
+            std::io::Error::from(std::io::ErrorKind::NotFound)
+        ));
+        assert_eq!(error.to_string(), "Texture file could not be read: materials/Rock023_1K-JPG/Rock023_1K-JPG_Color.jpg");
+        assert!(error.source().is_some());
+
+        let path = PathBuf::from("/assets/materials/Rock023_2K-JPG/Rock023_2K-JPG_Roughness.jpg");
+        let error = AmbientCGImportError(AmbientCGErrorType::Decode(
+            TextureChannel::Roughness,
+            path.clone(),
+            image::ImageError::IoError(std::io::Error::from(std::io::ErrorKind::NotFound))
+        ));
+        assert_eq!(error.to_string(), format!("Texture file could not be read: Roughness at {}", path.display()));
+        let error = AmbientCGImportError(AmbientCGErrorType::Decode(
+            TextureChannel::Roughness,
+            path.clone(),
+            image::ImageError::Unsupported(image::error::UnsupportedError::from(image::error::ImageFormatHint::Unknown))
+        ));
+        assert_eq!(error.to_string(), format!("Texture file could not be decoded: Roughness at {}", path.display()));
+    }
+
+    #[test]
+    fn sampler_follows_configured_address_mode() {
+        let config = AmbientCGConfig {
+            address_mode: ImageAddressMode::MirrorRepeat,
+            ..default()
+        };
+        let ImageSampler::Descriptor(descriptor) = repeat_sampler(&config) else {
+            panic!("expected a sampler descriptor");
+        };
+        assert!(matches!(descriptor.address_mode_u, ImageAddressMode::MirrorRepeat));
+        assert!(matches!(descriptor.address_mode_v, ImageAddressMode::MirrorRepeat));
+    }
+
+    #[test]
+    fn channel_samplers_override_the_default_per_channel() {
+        let config = AmbientCGConfig {
+            channel_samplers: HashMap::from([(TextureChannel::Displacement, ImageSamplerDescriptor::linear())]),
+            ..default()
+        };
+        // ImageFilterMode has no PartialEq
+        let is_linear = |sampler: ImageSampler| {
+            let ImageSampler::Descriptor(descriptor) = sampler else {
+                panic!("expected a sampler descriptor");
+            };
+            matches!(descriptor.mag_filter, ImageFilterMode::Linear)
+        };
+        let material = AmbientCGMaterial::DEFAULT;
+        assert!(is_linear(material.loader_settings(TextureChannel::Displacement, &config).sampler));
+        assert!(!is_linear(material.loader_settings(TextureChannel::Color, &config).sampler));
+
+        let config = AmbientCGConfig {
+            channel_samplers: HashMap::from([(TextureChannel::Metalness, ImageSamplerDescriptor::linear())]),
+            ..default()
+        };
+        let merged = labeled_image(flat_normal_image(), "Rock023_1K-JPG", "MetallicRoughness", &config, &AmbientCGState::default());
+        assert!(is_linear(merged.sampler));
+    }
+
+    #[test]
+    fn load_summary_counts_fallbacks_and_failures() {
+        let mut summary = AmbientCGLoadSummary::default();
+        summary.record(AmbientCGResolution::TwoK, Some(AmbientCGResolution::TwoK));
+        summary.record(AmbientCGResolution::FourK, Some(AmbientCGResolution::OneK));
+        summary.record(AmbientCGResolution::OneK, None);
+
+        assert_eq!(summary.to_string(), "Loaded 2 AmbientCG materials, 1 fell back in resolution, 1 failed");
+    }
+
+    #[test]
+    fn default_channel_layout_matches_gltf() {
+        let gray = |value: u8| DynamicImage::ImageLuma8(image::GrayImage::from_pixel(1, 1, image::Luma([value])));
+        let merge = |layout| MergeInputs::new(gray(200), gray(50), Some(gray(180)), None, false, layout, &AmbientCGConfig::default()).unwrap().merge();
+
+        assert_eq!(AmbientCGConfig::default().channel_layout, ChannelLayout::GLTF);
+        assert_eq!(merge(ChannelLayout::default()).get_pixel(0, 0).0, [180, 200, 50]);
+        let swapped = ChannelLayout {
+            red: PackedSource::Zero,
+            green: PackedSource::Metalness,
+            blue: PackedSource::Roughness
+        };
+        assert_eq!(merge(swapped).get_pixel(0, 0).0, [0, 50, 200]);
+    }
+
+    #[test]
+    fn materials_compare_and_hash_by_every_field() {
+        let material = AmbientCGMaterial {
+            name: "Rock023",
+            normal_strength: Some(0.5),
+            ..AmbientCGMaterial::DEFAULT
+        };
+        let mut set = HashSet::new();
+        set.insert(material.clone());
+
+        assert!(set.contains(&material));
+        assert_ne!(material, AmbientCGMaterial { normal_strength: Some(0.6), ..material.clone() });
+        assert_ne!(material, AmbientCGMaterial { mirror_u: true, ..material.clone() });
+    }
+
+    #[test]
+    fn displacement_only_textures_have_no_surface() {
+        let displacement_only = MaterialTextures {
+            thickness: Some(Handle::default()),
+            occlusion: Some(Handle::default()),
+            ..default()
+        };
+        assert!(!displacement_only.has_surface());
+
+        let with_color = MaterialTextures {
+            base_color: Some(Handle::default()),
+            ..displacement_only
+        };
+        assert!(with_color.has_surface());
+    }
+
+    #[test]
+    fn texture_paths_follow_naming_convention() {
+        let material = AmbientCGMaterial {
+            name: "Rock023",
+            resolution: AmbientCGResolution::TwoK,
+            subfolder: Some("rocks"),
+            ..AmbientCGMaterial::DEFAULT
+        };
+        let paths = material.texture_paths(&AmbientCGConfig::default());
+
+        assert_eq!(paths.folder, Path::new("materials/rocks/Rock023_2K-JPG"));
+        assert_eq!(paths.get(TextureChannel::NormalGL), Path::new("materials/rocks/Rock023_2K-JPG/Rock023_2K-JPG_NormalGL.jpg"));
+        assert_eq!(paths.get(TextureChannel::NormalDX), Path::new("materials/rocks/Rock023_2K-JPG/Rock023_2K-JPG_NormalDX.jpg"));
+    }
+
+    #[test]
+    fn flip_v_composes_with_uv_scale() {
+        let config = AmbientCGConfig {
+            flip_v: true,
+            ..default()
+        };
+        let material = AmbientCGMaterial::DEFAULT.configured_material(MaterialTextures::default(), Vec2::new(2.0, 3.0), &config);
+
+        assert_eq!(material.uv_transform.transform_point2(Vec2::new(0.5, 0.0)), Vec2::new(1.0, 3.0));
+        assert_eq!(material.uv_transform.transform_point2(Vec2::new(0.5, 1.0)), Vec2::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn negative_uv_scale_is_kept() {
+        let material = AmbientCGMaterial::DEFAULT.standard_material(MaterialTextures::default(), Vec2::new(-2.0, 3.0));
+        assert_eq!(material.uv_transform, Affine2::from_scale(Vec2::new(-2.0, 3.0)));
+    }
+
+    #[test]
+    fn mirror_u_negates_u_scale_and_flips_normal_x() {
+        let material = AmbientCGMaterial {
+            mirror_u: true,
+            ..AmbientCGMaterial::DEFAULT
+        };
+
+        assert_eq!(material.standard_material(MaterialTextures::default(), Vec2::new(2.0, 3.0)).uv_transform, Affine2::from_scale(Vec2::new(-2.0, 3.0)));
+        assert_eq!(material.standard_material(MaterialTextures::default(), Vec2::ZERO).uv_transform, Affine2::from_scale(Vec2::new(-1.0, 1.0)));
+        let adjustments = material.normal_adjustments(TextureChannel::NormalGL, &AmbientCGConfig::default());
+        assert!(adjustments.flip_x && !adjustments.flip_y);
+    }
+
+    #[test]
+    fn resolutions_sort_by_size() {
+        let mut resolutions = vec![AmbientCGResolution::EightK, AmbientCGResolution::OneK, AmbientCGResolution::TwelveK];
+        resolutions.sort();
+        assert_eq!(resolutions, [AmbientCGResolution::OneK, AmbientCGResolution::EightK, AmbientCGResolution::TwelveK]);
+        assert!(AmbientCGResolution::TwoK < AmbientCGResolution::FourK);
+    }
+
+    #[test]
+    fn png_asset_id_names_png_folder() {
+        let material = AmbientCGMaterial::from_asset_id("Rock023_2K-PNG", &AmbientCGConfig::default()).unwrap();
+        assert_eq!(material.variant, AmbientCGVariant::Png);
+        assert_eq!(material.folder_name(), "Rock023_2K-PNG");
+    }
+
+    #[test]
+    fn scalars_are_neutral_without_metallic_roughness_maps() {
+        let material = AmbientCGMaterial::DEFAULT.standard_material(MaterialTextures::default(), Vec2::ZERO);
+
+        assert_eq!(material.metallic, 0.0);
+        assert_eq!(material.perceptual_roughness, StandardMaterial::default().perceptual_roughness);
+    }
+
+    #[test]
+    fn roughness_map_alone_does_not_make_material_metallic() {
+        let textures = MaterialTextures {
+            metallic_roughness: Some(Handle::default()),
+            has_roughness: true,
+            ..default()
+        };
+        let material = AmbientCGMaterial::DEFAULT.standard_material(textures, Vec2::ZERO);
+
+        assert_eq!(material.metallic, 0.0);
+        assert_eq!(material.perceptual_roughness, 1.0);
+    }
+
+    #[test]
+    fn scalars_are_driven_by_maps_when_present() {
+        let textures = MaterialTextures {
+            metallic_roughness: Some(Handle::default()),
+            has_metalness: true,
+            has_roughness: true,
+            ..default()
+        };
+        let material = AmbientCGMaterial::DEFAULT.standard_material(textures, Vec2::ZERO);
+
+        assert_eq!(material.metallic, 1.0);
+        assert_eq!(material.perceptual_roughness, 1.0);
+    }
+}
@@ -0,0 +1,40 @@
+//! Offline helper copying loose textures into the `{name}_{resolution}-JPG` layout the loader expects.
+
+use std::io;
+use std::path::Path;
+
+use crate::{AmbientCGMaterial, AmbientCGResolution, TextureChannel};
+
+/// Where `organize` files a loose texture: the channel of material `name` at `resolution`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrganizedTexture {
+    pub name: String,
+    pub resolution: AmbientCGResolution,
+    pub channel: TextureChannel
+}
+
+/// Copies the loose textures directly inside `src` into material folders under `dst`, named after the AmbientCG
+/// convention so they load like downloaded materials, e.g. `dst/Brick001_2K-JPG/Brick001_2K-JPG_Roughness.png`.
+/// `mapping` is called with each file's name and returns where it goes, files it returns `None` for are skipped.
+/// Extensions are kept, lowercased, since every map is probed as `.jpg`, `.jpeg` and `.png`.
+pub fn organize(src: &Path, dst: &Path, mapping: impl Fn(&Path) -> Option<OrganizedTexture>) -> io::Result<()> {
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let file_name = entry.file_name();
+        let Some(texture) = mapping(Path::new(&file_name)) else {
+            continue;
+        };
+        let extension = Path::new(&file_name)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let folder_name = AmbientCGMaterial { name: &texture.name, resolution: texture.resolution, ..AmbientCGMaterial::DEFAULT }.folder_name();
+        let folder = dst.join(&folder_name);
+        std::fs::create_dir_all(&folder)?;
+        std::fs::copy(entry.path(), folder.join(format!("{}_{}.{}", folder_name, texture.channel.suffix(), extension)))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_ambient_cg::*;
+
+const TEST_001: AmbientCGMaterial = AmbientCGMaterial {
+    name: "Test001",
+    resolution: AmbientCGResolution::OneK,
+    ..AmbientCGMaterial::DEFAULT
+};
+
+#[test]
+fn asset_root_resolves_fixtures_without_env_var() {
+    let asset_root = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets");
+    let config = AmbientCGConfig { asset_root: Some(asset_root.into()), ..default() };
+    assert!(config.discover().contains(&("Test001".to_string(), AmbientCGResolution::OneK, AmbientCGVariant::Jpg)));
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin { file_path: asset_root.to_string(), ..default() },
+        ImagePlugin::default(),
+        AmbientCGPlugin { config }
+    ))
+        .init_asset::<StandardMaterial>();
+    app.finish();
+    app.cleanup();
+    let handle = app.world_mut()
+        .run_system_once(|asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.try_load(&asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+    let material = app.world().resource::<Assets<StandardMaterial>>().get(&handle).unwrap();
+    assert!(material.base_color_texture.is_some());
+}
@@ -0,0 +1,595 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use bevy::asset::UnapprovedPathMode;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_ambient_cg::*;
+
+const TEST_001: AmbientCGMaterial = AmbientCGMaterial {
+    name: "Test001",
+    resolution: AmbientCGResolution::OneK,
+    ..AmbientCGMaterial::DEFAULT
+};
+
+const ASSET_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets");
+
+/// The default config with its assets folder at `tests/assets`, so materials resolve from `tests/assets/materials`.
+fn config() -> AmbientCGConfig {
+    AmbientCGConfig { asset_root: Some(ASSET_ROOT.into()), ..default() }
+}
+
+/// Builds an app loading from `tests/assets`, through the asset server as well as through `config`.
+fn app() -> App {
+    app_with_assets(AssetPlugin::default())
+}
+
+fn app_with_assets(asset_plugin: AssetPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin { file_path: ASSET_ROOT.to_string(), ..asset_plugin },
+        ImagePlugin::default(),
+        AmbientCGPlugin { config: config() }
+    ))
+        .init_asset::<StandardMaterial>();
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn try_load(app: &mut App, material: AmbientCGMaterial<'static>) -> Result<Handle<StandardMaterial>, AmbientCGImportError> {
+    app.world_mut()
+        .run_system_once(move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            material.try_load(&asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+}
+
+fn standard_material(app: &App, handle: &Handle<StandardMaterial>) -> StandardMaterial {
+    app.world().resource::<Assets<StandardMaterial>>().get(handle).unwrap().clone()
+}
+
+#[test]
+fn load_finds_every_channel() {
+    let mut app = app();
+    let handle = try_load(&mut app, TEST_001).unwrap();
+    let material = standard_material(&app, &handle);
+
+    assert!(material.base_color_texture.is_some());
+    assert!(material.metallic_roughness_texture.is_some());
+    assert!(material.normal_map_texture.is_some());
+    assert!(material.occlusion_texture.is_some());
+    assert!(material.thickness_texture.is_some());
+}
+
+#[test]
+fn negotiation_steps_down_to_available_resolution() {
+    let mut app = app();
+    let handle = try_load(&mut app, AmbientCGMaterial {
+        resolution: AmbientCGResolution::FourK,
+        ..TEST_001
+    }).unwrap();
+    let material = standard_material(&app, &handle);
+
+    let base_color = material.base_color_texture.unwrap();
+    let path = app.world().resource::<AssetServer>().get_path(base_color.id()).unwrap();
+    assert!(path.path().starts_with("materials/Test001_1K-JPG"), "unexpected path {}", path);
+}
+
+#[test]
+fn available_resolutions_lists_folders_on_disk() {
+    let _app = app();
+    assert_eq!(TEST_001.available_resolutions(&config()).len(), 1);
+    assert!(AmbientCGMaterial {
+        name: "Missing001",
+        ..TEST_001
+    }.available_resolutions(&config()).is_empty());
+}
+
+#[test]
+fn swap_resolution_replaces_textures_in_place() {
+    let mut app = app();
+    let handle = try_load(&mut app, TEST_001).unwrap();
+    let before = standard_material(&app, &handle).base_color_texture.unwrap();
+
+    let swapped = handle.clone();
+    app.world_mut()
+        .run_system_once(move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.swap_resolution(&swapped, AmbientCGResolution::TwoK, &asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+    let material = standard_material(&app, &handle);
+
+    // only 1K exists on disk, so negotiation lands on the same files and every map survives the swap
+    assert_eq!(material.base_color_texture.unwrap().id(), before.id());
+    assert!(material.metallic_roughness_texture.is_some());
+    assert!(material.normal_map_texture.is_some());
+    assert!(material.occlusion_texture.is_some());
+}
+
+#[test]
+fn load_from_bytes_decodes_every_supplied_channel() {
+    let mut app = app();
+    let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/materials/Test001_1K-JPG");
+    let channels: HashMap<TextureChannel, Vec<u8>> = [TextureChannel::Color, TextureChannel::Metalness, TextureChannel::Roughness]
+        .into_iter()
+        .map(|channel| (channel, std::fs::read(format!("{}/Test001_1K-JPG_{}.jpg", folder, channel.suffix())).unwrap()))
+        .collect();
+
+    let handle = app.world_mut()
+        .run_system_once(move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.load_from_bytes(channels.clone(), &asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+    let material = standard_material(&app, &handle);
+
+    assert!(material.base_color_texture.is_some());
+    assert!(material.metallic_roughness_texture.is_some());
+    assert!(material.normal_map_texture.is_none());
+}
+
+#[test]
+fn load_into_fills_reserved_handle() {
+    let mut app = app();
+    let handle = app.world().resource::<Assets<StandardMaterial>>().reserve_handle();
+
+    let reserved = handle.clone();
+    app.world_mut()
+        .run_system_once(move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.load_into(&reserved, &asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+
+    assert!(standard_material(&app, &handle).base_color_texture.is_some());
+}
+
+#[test]
+fn displace_mesh_offsets_vertices_along_normals() {
+    let _app = app();
+    let mut mesh = Plane3d::default().mesh().build();
+    TEST_001.displace_mesh(&mut mesh, 2.0, &config()).unwrap();
+
+    let Some(bevy::render::mesh::VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+        panic!("plane has no positions");
+    };
+    // fixture displacement is a uniform 128, about half height
+    for position in positions {
+        assert!((position[1] - 2.0 * 128.0 / 255.0).abs() < 0.02, "unexpected height {}", position[1]);
+    }
+}
+
+#[test]
+fn displacement_only_folder_loads_no_surface_maps() {
+    let mut app = app();
+    let handle = try_load(&mut app, AmbientCGMaterial {
+        name: "Test002",
+        ..TEST_001
+    }).unwrap();
+    let material = standard_material(&app, &handle);
+
+    assert!(material.base_color_texture.is_none());
+    assert!(material.normal_map_texture.is_none());
+    assert!(material.thickness_texture.is_some());
+}
+
+#[test]
+fn load_from_overrides_materials_path() {
+    let mut app = app();
+    let load_from = |materials_path: &'static str| move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+        TEST_001.try_load_from(Path::new(materials_path), &asset_server, &mut materials, &config, &state)
+    };
+
+    assert!(app.world_mut().run_system_once(load_from("assets/materials")).unwrap().is_err());
+    let handle = app.world_mut().run_system_once(load_from("materials")).unwrap().unwrap();
+    assert!(standard_material(&app, &handle).base_color_texture.is_some());
+}
+
+#[test]
+fn load_textures_returns_handles_without_material() {
+    let mut app = app();
+    let textures = app.world_mut()
+        .run_system_once(|asset_server: Res<AssetServer>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.load_textures(&asset_server, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+
+    assert!(textures.base_color.is_some());
+    assert!(textures.metallic_roughness.is_some());
+    assert!(textures.normal_map.is_some());
+    assert_eq!(textures.resolution, AmbientCGResolution::OneK);
+    assert_eq!(app.world().resource::<Assets<StandardMaterial>>().len(), 0);
+}
+
+#[test]
+fn material_set_loads_variant_by_index() {
+    let mut app = app();
+    let load_variant = |index: usize| move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+        AmbientCGMaterialSet {
+            base_name: "Test00",
+            variants: &["1", "2"],
+            template: TEST_001
+        }.try_load_variant(index, &asset_server, &mut materials, &config, &state)
+    };
+
+    let first = app.world_mut().run_system_once(load_variant(0)).unwrap().unwrap();
+    assert!(standard_material(&app, &first).base_color_texture.is_some());
+    let second = app.world_mut().run_system_once(load_variant(1)).unwrap().unwrap();
+    assert!(standard_material(&app, &second).thickness_texture.is_some());
+    assert!(app.world_mut().run_system_once(load_variant(2)).unwrap().is_err());
+}
+
+#[test]
+fn spawn_ambient_cg_spawns_textured_mesh() {
+    let mut app = app();
+    app.init_asset::<Mesh>();
+    let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(Cuboid::default());
+
+    let spawned_mesh = mesh.clone();
+    let entity = app.world_mut()
+        .run_system_once(move |mut commands: Commands, asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            commands.spawn_ambient_cg(spawned_mesh.clone(), &TEST_001, &asset_server, &mut materials, Transform::from_xyz(1.0, 2.0, 3.0), &config, &state).id()
+        })
+        .unwrap();
+    let entity = app.world().entity(entity);
+
+    assert_eq!(entity.get::<Mesh3d>().unwrap().0, mesh);
+    assert_eq!(entity.get::<Transform>().unwrap().translation, Vec3::new(1.0, 2.0, 3.0));
+    let material = entity.get::<MeshMaterial3d<StandardMaterial>>().unwrap().0.clone();
+    assert!(standard_material(&app, &material).base_color_texture.is_some());
+}
+
+#[test]
+fn spawn_preview_spawns_sphere_with_material() {
+    let mut app = app();
+    app.init_asset::<Mesh>();
+
+    let entity = app.world_mut()
+        .run_system_once(|mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.spawn_preview(&mut commands, &mut meshes, &asset_server, &mut materials, &config, &state)
+        })
+        .unwrap();
+    let entity = app.world().entity(entity);
+
+    let mesh = &entity.get::<Mesh3d>().unwrap().0;
+    assert!(app.world().resource::<Assets<Mesh>>().get(mesh).unwrap().count_vertices() > 0);
+    let material = entity.get::<MeshMaterial3d<StandardMaterial>>().unwrap().0.clone();
+    assert!(standard_material(&app, &material).base_color_texture.is_some());
+}
+
+#[test]
+fn normal_dx_is_converted_when_normal_gl_is_missing() {
+    let mut app = app();
+    let test_003 = AmbientCGMaterial { name: "Test003", ..TEST_001 };
+    let handle = try_load(&mut app, test_003.clone()).unwrap();
+    let normal = standard_material(&app, &handle).normal_map_texture.unwrap();
+    app.update();
+
+    // the DirectX map's green of 64 points down, flipped into OpenGL's convention it points up
+    let image = app.world().resource::<Assets<Image>>().get(&normal).expect("DirectX normal maps are converted on the CPU");
+    assert!(image.data.as_ref().unwrap()[1] > 128);
+
+    let gl_only = try_load(&mut app, AmbientCGMaterial { normal_convention: NormalConvention::OpenGL, ..test_003 }).unwrap();
+    assert!(standard_material(&app, &gl_only).normal_map_texture.is_none());
+}
+
+#[derive(Asset, TypePath)]
+struct TerrainMaterial {
+    albedo: Option<Handle<Image>>,
+    normal: Option<Handle<Image>>
+}
+
+impl FromAmbientCGTextures for TerrainMaterial {
+    fn from_ambient_cg_textures(textures: MaterialTextures, _: &AmbientCGMaterial<'_>) -> Self {
+        TerrainMaterial {
+            albedo: textures.base_color,
+            normal: textures.normal_map
+        }
+    }
+}
+
+#[test]
+fn load_as_builds_custom_material() {
+    let mut app = app();
+    app.init_asset::<TerrainMaterial>();
+
+    let handle = app.world_mut()
+        .run_system_once(|asset_server: Res<AssetServer>, mut materials: ResMut<Assets<TerrainMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.try_load_as(&asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+    let material = app.world().resource::<Assets<TerrainMaterial>>().get(&handle).unwrap();
+
+    assert!(material.albedo.is_some());
+    assert!(material.normal.is_some());
+}
+
+#[test]
+fn texture_suffix_casing_is_ignored() {
+    let mut app = app();
+    let handle = try_load(&mut app, AmbientCGMaterial { name: "Test004", ..TEST_001 }).unwrap();
+    let base_color = standard_material(&app, &handle).base_color_texture.expect("_color.JPG matches the _Color probe");
+
+    assert!(base_color.path().unwrap().to_string().ends_with("Test004_1K-JPG_color.JPG"));
+}
+
+#[test]
+fn load_async_resolves_to_added_material() {
+    let mut app = app();
+    let world = app.world();
+    let load = TEST_001.load_async(world.resource::<AssetServer>(), world.resource::<AmbientCGConfig>(), world.resource::<AmbientCGState>());
+    let handle = bevy::tasks::block_on(load);
+    app.update();
+
+    let material = standard_material(&app, &handle);
+    assert!(material.base_color_texture.is_some());
+    assert!(material.metallic_roughness_texture.is_some());
+}
+
+#[test]
+fn load_from_absolute_reads_outside_assets_folder() {
+    // bevy only reads absolute paths once unapproved paths are allowed
+    let mut app = app_with_assets(AssetPlugin { unapproved_path_mode: UnapprovedPathMode::Deny, ..default() });
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/materials"));
+    let handle = app.world_mut()
+        .run_system_once(move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.try_load_from_absolute(dir, &asset_server, &mut materials, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+    let material = standard_material(&app, &handle);
+
+    assert!(material.base_color_texture.unwrap().path().unwrap().path().is_absolute());
+    // merging reads the roughness and metalness maps from the absolute path directly
+    assert!(material.metallic_roughness_texture.is_some());
+}
+
+#[test]
+fn describe_reports_texture_sources_without_loading() {
+    let _app = app();
+    let description = TEST_001.describe(&config()).unwrap();
+
+    assert_eq!(description.resolution, AmbientCGResolution::OneK);
+    assert!(matches!(&description.base_color_texture, Some(TextureSource::File(path)) if path.ends_with("Test001_1K-JPG_Color.jpg")));
+    let Some(TextureSource::Generated { label, sources }) = &description.metallic_roughness_texture else {
+        panic!("metalness and roughness are merged");
+    };
+    assert_eq!(label, "Test001_1K-JPG#MetallicRoughness");
+    assert_eq!(sources.len(), 2);
+    assert_eq!((description.metallic, description.perceptual_roughness), (1.0, 1.0));
+    assert_eq!(description.uv_transform, [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn describe_reports_downsampled_maps_as_generated() {
+    let _app = app();
+    let config = AmbientCGConfig { max_texture_size: Some(4), ..config() };
+    // Test005 pairs an 8x8 color map with a 4x4 roughness map, only the color map exceeds the limit
+    let description = AmbientCGMaterial { name: "Test005", ..TEST_001 }.describe(&config).unwrap();
+
+    let Some(TextureSource::Generated { label, sources }) = &description.base_color_texture else {
+        panic!("oversized color map is downsampled");
+    };
+    assert_eq!(label, "Test005_1K-JPG#Color");
+    assert!(sources[0].ends_with("Test005_1K-JPG_Color.jpg"));
+    assert!(matches!(&description.metallic_roughness_texture, Some(TextureSource::File(path)) if path.ends_with("Test005_1K-JPG_Roughness.jpg")));
+}
+
+#[test]
+fn audit_reports_channels_of_every_material() {
+    let _app = app();
+    let audits = config().audit();
+    let audit = |name: &str| audits.iter().find(|audit| audit.name == name).unwrap();
+
+    assert!(audit("Test001").is_complete());
+    assert_eq!(audit("Test001").missing(), vec![TextureChannel::NormalDX, TextureChannel::Transmission]);
+    assert_eq!(audit("Test002").channels, vec![TextureChannel::Displacement]);
+    // a DirectX normal map counts, a missing normal map doesn't
+    assert!(audit("Test003").is_complete());
+    assert!(!audit("Test005").is_complete());
+    assert!(audits.is_sorted());
+}
+
+#[test]
+fn lazy_material_loads_once_visible() {
+    let mut app = app();
+    let entity = app.world_mut().spawn((LazyAmbientCGMaterial(TEST_001), ViewVisibility::HIDDEN)).id();
+    app.update();
+    assert!(app.world().get::<MeshMaterial3d<StandardMaterial>>(entity).is_none());
+
+    app.world_mut().get_mut::<ViewVisibility>(entity).unwrap().set();
+    app.update();
+    let material = app.world().get::<MeshMaterial3d<StandardMaterial>>(entity).unwrap();
+    assert!(standard_material(&app, &material.0).base_color_texture.is_some());
+    assert!(app.world().get::<LazyAmbientCGMaterial>(entity).is_none());
+}
+
+#[test]
+fn preload_skips_materials_that_fail_to_load() {
+    let mut app = app();
+    app.insert_resource(PreloadAmbientCGMaterials(vec![AmbientCGMaterial { name: "Missing001", ..TEST_001 }, TEST_001]));
+    app.update();
+    app.update();
+
+    let progress = app.world().resource::<AmbientCGPreloadProgress>();
+    assert!(progress.is_finished());
+    assert_eq!(progress.handles.len(), 1);
+    assert!(!app.world().contains_resource::<PreloadAmbientCGMaterials>());
+}
+
+#[test]
+fn pack_metallic_roughness_returns_image_without_assets() {
+    let folder = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/materials/Test001_1K-JPG"));
+    let image = pack_metallic_roughness(
+        &folder.join("Test001_1K-JPG_Metalness.jpg"),
+        &folder.join("Test001_1K-JPG_Roughness.jpg"),
+        &config()
+    ).unwrap();
+    let pixel = &image.data.as_ref().unwrap()[0..4];
+    assert_eq!(pixel[0], 0, "occlusion is not packed");
+    assert!(pixel[1].abs_diff(200) <= 2, "unexpected roughness {}", pixel[1]);
+    assert!(pixel[2].abs_diff(50) <= 2, "unexpected metalness {}", pixel[2]);
+}
+
+#[test]
+fn organize_copies_loose_textures_into_material_folders() {
+    let root = std::env::temp_dir().join("bevy_ambient_cg_organize");
+    let (src, dst) = (root.join("loose"), root.join("materials"));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::write(src.join("brick_rough.PNG"), b"roughness").unwrap();
+    std::fs::write(src.join("notes.txt"), b"unrelated").unwrap();
+
+    organize(&src, &dst, |file| {
+        (file.to_str()? == "brick_rough.PNG").then(|| OrganizedTexture {
+            name: "Brick001".to_string(),
+            resolution: AmbientCGResolution::TwoK,
+            channel: TextureChannel::Roughness
+        })
+    }).unwrap();
+
+    let organized = dst.join("Brick001_2K-JPG/Brick001_2K-JPG_Roughness.png");
+    assert_eq!(std::fs::read(organized).unwrap(), b"roughness");
+    assert_eq!(std::fs::read_dir(dst.join("Brick001_2K-JPG")).unwrap().count(), 1);
+}
+
+#[test]
+fn changed_config_resource_applies_to_next_load() {
+    static PROBED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+    let mut app = app();
+    let mut config = app.world_mut().resource_mut::<AmbientCGConfig>();
+    config.materials_path = "missing".into();
+    config.on_file_probe = Some(|path, _| PROBED.lock().unwrap().push(path.to_path_buf()));
+    assert!(try_load(&mut app, TEST_001).is_err());
+
+    app.world_mut().resource_mut::<AmbientCGConfig>().materials_path = "materials".into();
+    assert!(try_load(&mut app, TEST_001).is_ok());
+    assert!(PROBED.lock().unwrap().iter().any(|path| path.ends_with("materials/Test001_1K-JPG/Test001_1K-JPG_Color.jpg")));
+}
+
+#[test]
+fn reused_materials_keep_their_uv_transform() {
+    let mut app = app();
+    app.world_mut().resource_mut::<AmbientCGConfig>().reuse_materials = true;
+    let mut load_with_uv_scale = |uv_scale: Vec2| app.world_mut()
+        .run_system_once(move |asset_server: Res<AssetServer>, mut materials: ResMut<Assets<StandardMaterial>>, config: Res<AmbientCGConfig>, state: Res<AmbientCGState>| {
+            TEST_001.try_load_with_uv_scale(&asset_server, &mut materials, uv_scale, &config, &state)
+        })
+        .unwrap()
+        .unwrap();
+
+    let small = load_with_uv_scale(Vec2::splat(2.0));
+    let large = load_with_uv_scale(Vec2::splat(8.0));
+    assert_ne!(small, large);
+    assert_eq!(load_with_uv_scale(Vec2::splat(2.0)), small);
+
+    let materials = app.world().resource::<Assets<StandardMaterial>>();
+    assert_eq!(materials.get(&small).unwrap().uv_transform.matrix2, Mat2::from_diagonal(Vec2::splat(2.0)));
+    assert_eq!(materials.get(&large).unwrap().uv_transform.matrix2, Mat2::from_diagonal(Vec2::splat(8.0)));
+}
+
+#[test]
+fn lenient_decode_resizes_mismatched_merge_inputs() {
+    // Test006 pairs an 8x8 metalness map with a 4x4 roughness map
+    let test_006 = AmbientCGMaterial { name: "Test006", ..TEST_001 };
+    let mut app = app();
+    let error = try_load(&mut app, test_006.clone()).unwrap_err();
+    assert!(matches!(error.error_type(), AmbientCGErrorType::DimensionMismatch(TextureChannel::Metalness, (8, 8), (4, 4))));
+
+    app.world_mut().resource_mut::<AmbientCGConfig>().lenient_decode = true;
+    let handle = try_load(&mut app, test_006).unwrap();
+    let metallic_roughness = standard_material(&app, &handle).metallic_roughness_texture.unwrap();
+    app.update();
+    let image = app.world().resource::<Assets<Image>>().get(&metallic_roughness).unwrap();
+    assert_eq!(image.size(), UVec2::splat(4));
+}
+
+#[test]
+fn lone_roughness_map_is_labeled_as_roughness() {
+    let mut app = app();
+    // downsampling the 4x4 roughness map generates it on the CPU, under the label of its channel
+    app.world_mut().resource_mut::<AmbientCGConfig>().max_texture_size = Some(2);
+    let handle = try_load(&mut app, AmbientCGMaterial { name: "Test005", ..TEST_001 }).unwrap();
+    let metallic_roughness = standard_material(&app, &handle).metallic_roughness_texture.unwrap();
+    app.update();
+
+    let image = app.world().resource::<Assets<Image>>().get(&metallic_roughness).unwrap();
+    assert_eq!(image.texture_descriptor.label, Some("Test005_1K-JPG#Roughness"));
+}
+
+#[test]
+fn loads_are_tracked_per_app() {
+    let mut loading = app();
+    let mut idle = app();
+    let handle = try_load(&mut loading, TEST_001).unwrap();
+    // the idle app syncs first, it must not pick up the other app's load
+    idle.update();
+    loading.update();
+
+    assert!(loading.world().resource::<AmbientCGRegistry>().get(&handle).is_some());
+    assert_eq!(loading.world().resource::<AmbientCGLoadSummary>().loaded, 1);
+    assert!(idle.world().resource::<AmbientCGRegistry>().entries.is_empty());
+    assert_eq!(idle.world().resource::<AmbientCGLoadSummary>().loaded, 0);
+}
+
+#[test]
+fn directory_listings_report_their_matches() {
+    static PROBED: Mutex<Vec<(PathBuf, bool)>> = Mutex::new(Vec::new());
+    let mut app = app();
+    app.world_mut().resource_mut::<AmbientCGConfig>().on_file_probe = Some(|path, exists| PROBED.lock().unwrap().push((path.to_path_buf(), exists)));
+    try_load(&mut app, AmbientCGMaterial { name: "Test004", ..TEST_001 }).unwrap();
+
+    let probed = PROBED.lock().unwrap();
+    assert!(probed.contains(&(PathBuf::from(ASSET_ROOT).join("materials"), true)));
+    assert!(probed.contains(&(PathBuf::from(ASSET_ROOT).join("materials/Test004_1K-JPG"), true)));
+    assert!(probed.contains(&(PathBuf::from(ASSET_ROOT).join("materials/Test004_1K-JPG/Test004_1K-JPG_color.JPG"), true)));
+}
+
+#[test]
+fn missing_material_lists_expected_files() {
+    let mut app = app();
+    let error = try_load(&mut app, AmbientCGMaterial {
+        name: "Missing001",
+        ..TEST_001
+    }).unwrap_err();
+
+    let AmbientCGErrorType::MissingFiles(files) = error.error_type() else {
+        panic!("unexpected error {}", error);
+    };
+    assert_eq!(files.len(), TextureChannel::ALL.len());
+    assert!(files.contains(&(TextureChannel::Color, "materials/Missing001_1K-JPG/Missing001_1K-JPG_Color.jpg".into())));
+    assert!(error.to_string().contains("NormalGL at materials/Missing001_1K-JPG/Missing001_1K-JPG_NormalGL.jpg"));
+}
+
+#[test]
+fn merge_packs_roughness_into_green_and_metalness_into_blue() {
+    let mut app = app();
+    let handle = try_load(&mut app, TEST_001).unwrap();
+    let metallic_roughness = standard_material(&app, &handle).metallic_roughness_texture.unwrap();
+
+    // generated images reach Assets<Image> once the asset server's events are processed
+    app.update();
+
+    let image = app.world().resource::<Assets<Image>>().get(&metallic_roughness).unwrap();
+    let pixel = &image.data.as_ref().unwrap()[0..4];
+    // fixture roughness is a uniform 200 and metalness a uniform 50
+    assert!(pixel[1].abs_diff(200) <= 2, "unexpected roughness {}", pixel[1]);
+    assert!(pixel[2].abs_diff(50) <= 2, "unexpected metalness {}", pixel[2]);
+}
+
+#[test]
+fn generated_images_are_labeled_after_their_material() {
+    let mut app = app();
+    let handle = try_load(&mut app, TEST_001).unwrap();
+    let metallic_roughness = standard_material(&app, &handle).metallic_roughness_texture.unwrap();
+    app.update();
+
+    let image = app.world().resource::<Assets<Image>>().get(&metallic_roughness).unwrap();
+    assert_eq!(image.texture_descriptor.label, Some("Test001_1K-JPG#MetallicRoughness"));
+}